@@ -0,0 +1,38 @@
+//! Runs Klaus Dormann's 6502 decimal test without the debugger and reports whether every
+//! `ADC` and `SBC` result and flag matched the NMOS 6502's.
+//!
+//! ```text
+//! cargo run --release --example run_decimal_test
+//! ```
+
+use rust_6502::cpu::run_exit::RunExit;
+use rust_6502::cpu::Cpu;
+
+const DECIMAL_TEST: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../test_suites/6502_decimal_test.bin"
+);
+
+/// The address of `ERROR` in 6502_decimal_test.lst, which the test clears when it passes.
+const ERROR_ADDRESS: usize = 0x000B;
+
+fn main() {
+    let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+
+    cpu.power_up();
+
+    cpu.memory
+        .load_raw_file(DECIMAL_TEST, 0x0000)
+        .expect("unable to load the decimal test");
+
+    let exit = cpu.run_headless();
+    let error = cpu.memory.get_8_bit_value(ERROR_ADDRESS);
+
+    match (exit, error) {
+        (RunExit::Trapped(_), 0) => println!("PASS after {} cycles", cpu.cycles()),
+        _ => println!("FAIL with {:?} after {} cycles", exit, cpu.cycles()),
+    }
+
+    assert!(matches!(exit, RunExit::Trapped(_)));
+    assert_eq!(error, 0);
+}
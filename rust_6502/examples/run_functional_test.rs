@@ -0,0 +1,37 @@
+//! Runs Klaus Dormann's 6502 functional test without the debugger and reports whether
+//! it reached the success trap.
+//!
+//! ```text
+//! cargo run --release --example run_functional_test
+//! ```
+
+use rust_6502::cpu::run_exit::RunExit;
+use rust_6502::cpu::Cpu;
+
+const FUNCTIONAL_TEST: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../test_suites/6502_functional_test.bin"
+);
+
+/// The address of the `success` trap in 6502_functional_test.lst.
+const SUCCESS_ADDRESS: u16 = 0x3469;
+
+fn main() {
+    let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+
+    cpu.power_up();
+    cpu.success_address = Some(SUCCESS_ADDRESS);
+
+    cpu.memory
+        .load_raw_file(FUNCTIONAL_TEST, 0x0000)
+        .expect("unable to load the functional test");
+
+    let exit = cpu.run_headless();
+
+    match exit {
+        RunExit::Success => println!("PASS after {} cycles", cpu.cycles()),
+        _ => println!("FAIL with {:?} after {} cycles", exit, cpu.cycles()),
+    }
+
+    assert_eq!(exit, RunExit::Success);
+}
@@ -1,23 +1,81 @@
+pub mod breakpoint;
+pub mod clock;
+pub mod debug_command;
+pub mod decoded_instruction;
+pub mod error;
 pub mod instruction;
+pub mod interrupt;
+pub mod kernal_stubs;
 pub mod memory;
+pub mod peripheral;
 pub mod registers;
+pub mod replay;
+pub mod run_exit;
+pub mod snapshot;
 pub mod status_flags;
-
-use indexable_str::IndexableStr;
-use regex::Regex;
-use std::time::{Duration, Instant};
-
+pub mod trace;
+pub mod variant;
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+use breakpoint::{BreakpointInfo, BreakpointKind, ConditionalBreakpoint, WatchKind, Watchpoint};
+use clock::{Clock, SystemClock};
+use debug_command::{parse_debug_command, DebugCommand};
+use decoded_instruction::DecodedInstruction;
+use error::CpuError;
 use instruction::AddressingMode;
 use instruction::ExecutionReturnValues;
 use instruction::Instruction;
-use memory::Memory;
-use registers::Registers;
-use status_flags::StatusFlags;
+use instruction::{is_jam_opcode, INSTRUCTION_SET, WDC_65C02_INSTRUCTION_SET};
+use interrupt::InterruptKind;
+use kernal_stubs::KernalStubs;
+use memory::{Memory, MemoryLoadError};
+use peripheral::Peripheral;
+use registers::{Register, Registers};
+use replay::{Input, InputEvent, InputLog, Recording, Replaying};
+use run_exit::RunExit;
+use snapshot::{CpuSnapshot, RegistersSnapshot};
+use status_flags::{Flag, StatusFlags};
+use trace::TraceRecord;
+use variant::CpuVariant;
 
 const NMI_VECTOR: usize = 0xFFFA;
 const RESET_VECTOR: usize = 0xFFFC;
 const IRQ_BRK_VECTOR: usize = 0xFFFE;
 const STACK_BASE_ADDRESS: usize = 0x0100;
+const CHRIN_ADDRESS: usize = 0xFFCF;
+const CHROUT_ADDRESS: usize = 0xFFD2;
+const STOP_ADDRESS: usize = 0xFFE1;
+const JSR_OPCODE: u8 = 0x20;
+const RTS_OPCODE: u8 = 0x60;
+const RTI_OPCODE: u8 = 0x40;
+
+/// The address `Cpu::eval()` loads its code at.
+pub const EVAL_ADDRESS: u16 = 0x0200;
+
+type TraceCallback = Box<dyn FnMut(&TraceRecord)>;
+type ExecHook = Box<dyn FnMut(&mut Cpu, &DecodedInstruction)>;
+type TrapHandler = Box<dyn FnMut(&mut Cpu)>;
+
+/// The number of commands the debugger's `H` command lists.
+const DEBUGGER_HISTORY_LENGTH: usize = 20;
+
+/// The debugger commands and their descriptions, used to render the help text.
+const DEBUGGER_COMMANDS: [(&str, &str); 11] = [
+    ("B $XXXX", "Toggle Breakpoint"),
+    ("D $XXXX", "Display Memory, optionally followed by a length"),
+    ("K", "Display Stack"),
+    ("S", "Step, optionally followed by a number of instructions"),
+    ("T", "Toggle Trapping"),
+    ("X", "Execute"),
+    ("P", "Pause"),
+    ("V", "Toggle Verbose Display"),
+    ("H", "Command History"),
+    ("Q", "Quit"),
+    ("?", "Help"),
+];
 
 /// Emulates a 6502 microprocessor.
 pub struct Cpu {
@@ -25,14 +83,64 @@ pub struct Cpu {
     pub registers: Registers,
     /// The 6502's memory, which is 64K in size.
     pub memory: Memory,
+    /// The member of the 6502 family being emulated. Defaults to `CpuVariant::Nmos6502`.
+    pub variant: CpuVariant,
     /// Set to `true` to trigger an NMI.
     pub nmi_triggered: bool,
     /// Set to `true` to trigger an interrupt.
     pub irq_triggered: bool,
     /// A set of breakpoint addresses.
     pub breakpoints: Vec<u16>,
+    /// Set to `true` to make `Cpu::run()` return `RunExit::BreakWithoutHandler` instead of
+    /// executing a `BRK` while the IRQ/BRK vector is $0000.
+    pub halt_on_unhandled_brk: bool,
+    /// Set to `true` to execute unrecognized opcodes as NOPs instead of stopping. Each one
+    /// takes 2 cycles and skips the number of bytes its opcode most likely has.
+    pub undefined_opcodes_as_nops: bool,
+    /// Set when an NMOS cpu executes a JAM opcode, such as $02, which locks it up until
+    /// `Cpu::power_up()` or `Cpu::reset()` is called. While it's set no instruction executes and `Cpu::run()`
+    /// and `Cpu::run_headless()` return `RunExit::Jammed`.
+    pub halted: bool,
+    /// The address of a test suite's success trap, which varies with how the suite was
+    /// assembled. `Cpu::run_headless()` returns `RunExit::Success` instead of
+    /// `RunExit::Trapped` when an instruction traps there. Defaults to `None`.
+    pub success_address: Option<u16>,
+    /// The deepest `Cpu::call_depth()` allowed before `Cpu::run()` and `Cpu::run_headless()`
+    /// return `RunExit::RecursionLimit`, which catches runaway recursion before it wraps the
+    /// stack. Defaults to `None`, which doesn't limit the depth.
+    pub max_call_depth: Option<u32>,
+    /// The number of times an instruction must jump or branch to itself, without changing
+    /// any register, before the debugger reports a trap. Defaults to 1.
+    pub trap_threshold: u32,
+    /// Set to `false` to stop the debugger from showing the registers and disassembly
+    /// before each prompt. The `V` command toggles it. Defaults to `true`.
+    pub debugger_verbose: bool,
+    breakpoint_hits: HashMap<u16, u64>,
+    conditional_breakpoints: Vec<ConditionalBreakpoint>,
+    watchpoints: Vec<Watchpoint>,
+    kernal_stubs: Option<KernalStubs>,
+    traps: HashMap<u16, TrapHandler>,
+    peripherals: Vec<Box<dyn Peripheral>>,
+    skip_next_irq: bool,
+    rdy: bool,
+    debug_stepping: bool,
+    debug_steps_remaining: u16,
+    debug_trapping: bool,
+    debug_last_state: Option<(u16, u8, u8, u8, u8, u8)>,
+    debug_repeat_count: u32,
+    debug_history: Vec<String>,
+    trace_callback: Option<TraceCallback>,
+    pre_exec_hook: Option<ExecHook>,
+    post_exec_hook: Option<ExecHook>,
+    flag_watches: Vec<(Flag, bool)>,
+    trace_filter: Option<RangeInclusive<u16>>,
+    recording: Option<Recording>,
+    replaying: Option<Replaying>,
+    cycles: u64,
+    instructions: u64,
+    call_depth: u32,
     cycle_duration: f64,
-    hexadecimal_number_pattern: Regex,
+    clock: Box<dyn Clock>,
 }
 
 impl Cpu {
@@ -51,21 +159,129 @@ impl Cpu {
     /// let cpu = Cpu::new(0x0400, 1_789_773.0);
     /// ```
     pub fn new(reset_address: u16, clock_speed: f64) -> Cpu {
+        Cpu::with_memory(reset_address, clock_speed, Memory::new())
+    }
+
+    /// Creates a new Cpu object that uses memory prepared elsewhere.
+    /// The reset vector is still written into the memory.
+    ///
+    /// # Arguments
+    /// * `reset_address` - Will be stored a memory location 0xFFFC and program execution will begin at this address by calling `Cpu::run()`.
+    /// * `clock_speed` - This is the clock speed that the cpu should try to emulate.
+    /// * `memory` - The memory the cpu uses.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::memory::Memory;
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut memory = Memory::new();
+    /// memory.contents[0x0400] = 0xEA;
+    ///
+    /// let cpu = Cpu::with_memory(0x0400, 1_789_773.0, memory);
+    ///
+    /// assert_eq!(cpu.memory.contents[0x0400], 0xEA);
+    /// ```
+    pub fn with_memory(reset_address: u16, clock_speed: f64, memory: Memory) -> Cpu {
         let mut cpu = Cpu {
             registers: Registers::new(),
-            memory: Memory::new(),
+            memory,
+            variant: CpuVariant::Nmos6502,
             cycle_duration: 1.0 / clock_speed,
             breakpoints: Vec::new(),
+            halt_on_unhandled_brk: false,
+            undefined_opcodes_as_nops: false,
+            halted: false,
+            success_address: None,
+            max_call_depth: None,
+            call_depth: 0,
+            trap_threshold: 1,
+            debugger_verbose: true,
+            breakpoint_hits: HashMap::new(),
+            conditional_breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+            kernal_stubs: None,
+            traps: HashMap::new(),
+            peripherals: Vec::new(),
+            skip_next_irq: false,
+            rdy: true,
+            debug_stepping: true,
+            debug_steps_remaining: 0,
+            debug_trapping: true,
+            debug_last_state: None,
+            debug_repeat_count: 0,
+            debug_history: Vec::new(),
+            trace_callback: None,
+            pre_exec_hook: None,
+            post_exec_hook: None,
+            flag_watches: Vec::new(),
+            trace_filter: None,
+            recording: None,
+            replaying: None,
+            cycles: 0,
+            instructions: 0,
+            clock: Box::new(SystemClock::new()),
             nmi_triggered: false,
             irq_triggered: false,
-            hexadecimal_number_pattern: Regex::new(r"^\$[0-9A-Fa-f]{1,4}$").unwrap(),
         };
 
+        debug_assert_eq!(Cpu::verify_instruction_table(), Ok(()));
+
         cpu.memory.set_16_bit_value(RESET_VECTOR, reset_address);
 
         cpu
     }
 
+    /// Checks that the instruction tables are sorted by opcode and have no duplicates,
+    /// which `Instruction::binary_search()` relies on. `Cpu::new()` runs this in debug builds.
+    ///
+    /// Returns a description of the first problem found.
+    pub fn verify_instruction_table() -> Result<(), String> {
+        for table in [&INSTRUCTION_SET[..], &WDC_65C02_INSTRUCTION_SET[..]] {
+            for pair in table.windows(2) {
+                if pair[0].opcode == pair[1].opcode {
+                    return Err(format!("Duplicate opcode ${:02X}.", pair[1].opcode));
+                }
+
+                if pair[0].opcode > pair[1].opcode {
+                    return Err(format!(
+                        "Opcode ${:02X} is out of order after ${:02X}.",
+                        pair[1].opcode, pair[0].opcode
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Resets the registers, the cycle and instruction counts, and any pending interrupts
+    /// to their state after `Cpu::new()`, so one `Cpu` can be reused across test cases.
+    /// Memory is left alone; call `Memory::clear()` to zero it too.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.registers.a = 0x12;
+    ///
+    /// cpu.clear_registers();
+    /// cpu.memory.clear();
+    ///
+    /// assert_eq!(cpu.registers.a, 0x00);
+    /// assert_eq!(cpu.memory.contents[0xFFFD], 0x00);
+    /// ```
+    pub fn clear_registers(&mut self) {
+        self.registers = Registers::new();
+        self.cycles = 0;
+        self.instructions = 0;
+        self.call_depth = 0;
+        self.halted = false;
+        self.nmi_triggered = false;
+        self.irq_triggered = false;
+    }
+
     /// Powers up the cpu. This method disables interrupts, sets the stack pointer to 0x01FF,
     /// and sets the program counter to the address in the reset vector (0xFFFC).
     pub fn power_up(&mut self) {
@@ -73,6 +289,187 @@ impl Cpu {
         self.registers.p.break_flag = false;
         self.registers.sp = 0xff;
         self.registers.pc = self.memory.get_16_bit_value(RESET_VECTOR);
+        self.call_depth = 0;
+        self.halted = false;
+    }
+
+    /// Resets the cpu the way the RESET line does. Unlike `Cpu::power_up()`, memory and the
+    /// other registers are left alone. The reset sequence performs three stack reads that
+    /// decrement the stack pointer without writing, sets the interrupt disable flag and loads
+    /// the program counter from the reset vector (0xFFFC). The 65C02 also clears the decimal
+    /// flag. It takes 7 cycles and clears `halted`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.power_up();
+    ///
+    /// cpu.registers.pc = 0x1234;
+    /// cpu.reset();
+    ///
+    /// assert_eq!(cpu.registers.pc, 0x0400);
+    /// assert_eq!(cpu.registers.sp, 0xFC);
+    /// ```
+    pub fn reset(&mut self) {
+        self.registers.sp = self.registers.sp.wrapping_sub(3);
+        self.registers.p.interrupt_disable_flag = true;
+
+        if self.variant == CpuVariant::Wdc65C02 {
+            self.registers.p.decimal_flag = false;
+        }
+
+        self.registers.pc = self.memory.get_16_bit_value(RESET_VECTOR);
+        self.cycles += 7;
+        self.call_depth = 0;
+        self.halted = false;
+    }
+
+    /// Loads a flat binary file at the address stored in the reset vector (0xFFFC).
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the binary file.
+    ///
+    /// Returns the number of bytes loaded or a `MemoryLoadError`.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x2000, 1_789_773.0);
+    ///
+    /// let length = cpu.load_program_at_reset("program.bin").unwrap();
+    /// ```
+    pub fn load_program_at_reset(&mut self, file_path: &str) -> Result<usize, MemoryLoadError> {
+        let reset_address = self.memory.get_16_bit_value(RESET_VECTOR);
+
+        self.memory.load_raw_file(file_path, reset_address as usize)
+    }
+
+    /// Captures the registers, pending interrupts, cycle counter and memory.
+    ///
+    /// Memory hooks, breakpoints and other configuration aren't part of the snapshot.
+    pub fn snapshot(&self) -> CpuSnapshot {
+        CpuSnapshot {
+            a: self.registers.a,
+            x: self.registers.x,
+            y: self.registers.y,
+            p: self.registers.p.to_byte(),
+            sp: self.registers.sp,
+            pc: self.registers.pc,
+            nmi_triggered: self.nmi_triggered,
+            irq_triggered: self.irq_triggered,
+            cycles: self.cycles,
+            memory: self.memory.contents.to_vec(),
+        }
+    }
+
+    /// Restores the state captured by `Cpu::snapshot()`.
+    ///
+    /// # Arguments
+    /// * `snapshot` - The snapshot to restore.
+    pub fn restore(&mut self, snapshot: &CpuSnapshot) {
+        self.registers.a = snapshot.a;
+        self.registers.x = snapshot.x;
+        self.registers.y = snapshot.y;
+        self.registers.p.from_byte(snapshot.p);
+        self.registers.sp = snapshot.sp;
+        self.registers.pc = snapshot.pc;
+        self.nmi_triggered = snapshot.nmi_triggered;
+        self.irq_triggered = snapshot.irq_triggered;
+        self.cycles = snapshot.cycles;
+        self.memory.contents.copy_from_slice(&snapshot.memory);
+    }
+
+    /// Captures the registers and cycle counter, but not memory.
+    pub fn registers_snapshot(&self) -> RegistersSnapshot {
+        RegistersSnapshot {
+            a: self.registers.a,
+            x: self.registers.x,
+            y: self.registers.y,
+            p: self.registers.p.to_byte(),
+            sp: self.registers.sp,
+            pc: self.registers.pc,
+            cycles: self.cycles,
+        }
+    }
+
+    /// Restores the state captured by `Cpu::registers_snapshot()`.
+    ///
+    /// # Arguments
+    /// * `snapshot` - The snapshot to restore.
+    pub fn restore_registers(&mut self, snapshot: &RegistersSnapshot) {
+        self.registers.a = snapshot.a;
+        self.registers.x = snapshot.x;
+        self.registers.y = snapshot.y;
+        self.registers.p.from_byte(snapshot.p);
+        self.registers.sp = snapshot.sp;
+        self.registers.pc = snapshot.pc;
+        self.cycles = snapshot.cycles;
+    }
+
+    /// Saves a snapshot of the cpu to a JSON file. Requires the `serde` feature.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the file to write.
+    #[cfg(feature = "serde")]
+    pub fn save_state(&self, file_path: &str) -> std::io::Result<()> {
+        let json = serde_json::to_string(&self.snapshot())?;
+
+        std::fs::write(file_path, json)
+    }
+
+    /// Restores a snapshot saved by `Cpu::save_state()`. Requires the `serde` feature.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the file to read.
+    #[cfg(feature = "serde")]
+    pub fn load_state(&mut self, file_path: &str) -> std::io::Result<()> {
+        let json = std::fs::read_to_string(file_path)?;
+
+        let snapshot: CpuSnapshot = serde_json::from_str(&json)?;
+
+        if snapshot.memory.len() != self.memory.contents.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "The snapshot's memory isn't 64K in size.",
+            ));
+        }
+
+        self.restore(&snapshot);
+
+        Ok(())
+    }
+
+    /// Sets every register in one call, which is handy when preparing a test.
+    ///
+    /// # Arguments
+    /// * `entry` - The new program counter.
+    /// * `flags` - The status register as a byte.
+    /// * `a` - The accumulator.
+    /// * `x` - The X register.
+    /// * `y` - The Y register.
+    /// * `sp` - The stack pointer.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// cpu.prepare_test(0x0600, 0x01, 0x10, 0x20, 0x30, 0xF0);
+    ///
+    /// assert_eq!(cpu.registers.pc, 0x0600);
+    /// assert!(cpu.registers.p.carry_flag);
+    /// ```
+    pub fn prepare_test(&mut self, entry: u16, flags: u8, a: u8, x: u8, y: u8, sp: u8) {
+        self.registers.pc = entry;
+        self.registers.p.from_byte(flags);
+        self.registers.a = a;
+        self.registers.x = x;
+        self.registers.y = y;
+        self.registers.sp = sp;
     }
 
     /// Executes the opcode at the location of the program counter.
@@ -99,9 +496,87 @@ impl Cpu {
     /// assert_eq!(cpu.registers.a, 0xFF);
     /// ```
     pub fn execute_opcode(&mut self) -> Option<ExecutionReturnValues> {
-        let instruction = self.get_instruction_for_opcode(self.registers.pc as usize)?;
+        self.trace();
+
+        self.call_kernal_stub();
+
+        if let Some(execution_return_values) = self.call_trap() {
+            return Some(execution_return_values);
+        }
+
+        let Some(instruction) = self.get_instruction_for_opcode(self.registers.pc as usize) else {
+            return self.execute_undefined_opcode();
+        };
+
+        let accessed_io = self
+            .get_effective_address(instruction)
+            .is_some_and(|address| self.memory.is_hooked(address));
+
+        let mut execution_return_values = (instruction.execute)(self, instruction);
+
+        execution_return_values.accessed_io = accessed_io;
+
+        Some(execution_return_values)
+    }
+
+    /// Installs `RTS` stubs at the KERNAL's `CHRIN` ($FFCF), `CHROUT` ($FFD2) and
+    /// `STOP` ($FFE1) entry points. When the program counter reaches one of them,
+    /// the matching closure is called before the `RTS` returns to the caller.
+    /// This allows simple C64 programs to run without a KERNAL ROM.
+    ///
+    /// # Arguments
+    /// * `stubs` - The closures to call for each KERNAL routine.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    /// use rust_6502::cpu::kernal_stubs::KernalStubs;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// cpu.install_kernal_stubs(KernalStubs {
+    ///     chrout: Some(Box::new(|c: u8| print!("{}", c as char))),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// assert_eq!(cpu.memory.contents[0xFFD2], 0x60);
+    /// ```
+    pub fn install_kernal_stubs(&mut self, stubs: KernalStubs) {
+        for address in [CHRIN_ADDRESS, CHROUT_ADDRESS, STOP_ADDRESS] {
+            self.memory.contents[address] = RTS_OPCODE;
+        }
+
+        self.kernal_stubs = Some(stubs);
+    }
 
-        Some((instruction.execute)(self, instruction))
+    /// Replaces the subroutine at an address with native code. When the program counter
+    /// reaches `address`, `handler` is called instead of the instruction there and then an
+    /// `RTS` returns to the caller, taking the `RTS`'s 6 cycles. Installing another handler
+    /// at the same address replaces the first.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the subroutine.
+    /// * `handler` - The closure to call.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.power_up();
+    ///
+    /// // JSR $E000, then loop forever.
+    /// cpu.memory
+    ///     .save_u8_vector_into_memory(0x0400, vec![0x20, 0x00, 0xE0, 0x4C, 0x03, 0x04]);
+    ///
+    /// cpu.install_trap(0xE000, |cpu: &mut Cpu| cpu.registers.a = 0x42);
+    /// cpu.step_until_cycles(12).unwrap();
+    ///
+    /// assert_eq!(cpu.registers.a, 0x42);
+    /// assert_eq!(cpu.registers.pc, 0x0403);
+    /// ```
+    pub fn install_trap(&mut self, address: u16, handler: impl FnMut(&mut Cpu) + 'static) {
+        self.traps.insert(address, Box::new(handler));
     }
 
     /// This will start program execution at the location in the cpu's reset vector (0xFFFC).
@@ -109,37 +584,58 @@ impl Cpu {
     /// 
     /// # Arguments
     /// * `debugger` - A function callback for performing debugging.
+    ///
+    /// Returns the `RunExit` explaining why execution stopped.
     /// 
     /// # Examples
     /// It's impractical to try to write an example of this method that 
     /// rustdoc can execute. Therefore, please refer to README.MD in the
     /// the repository for examples of using `Cpu::run()` with and without
     /// debugging.
-    pub fn run(&mut self, debugger: Option<fn(&str) -> String>) {
+    pub fn run(&mut self, debugger: Option<fn(&str) -> String>) -> RunExit {
         let debug = debugger.is_some();
-        let mut stepping = true;
-        let mut trap = true;
-        let mut last_address = 0x0000;
 
         loop {
-            if self.nmi_triggered
-                || (self.irq_triggered && !self.registers.p.interrupt_disable_flag)
-            {
-                self.handle_interrupts();
-            }
+            self.poll_interrupts();
 
             if debug {
-                let trap_hit = trap && self.registers.pc == last_address;
+                // The trapped instruction can only be a jump or branch to itself,
+                // which doesn't write memory, so comparing the registers is enough.
+                // A stalled cpu stays at the same address without being trapped.
+                let state = self.trap_state();
+
+                if self.rdy && self.debug_last_state == Some(state) {
+                    self.debug_repeat_count += 1;
+                } else {
+                    self.debug_repeat_count = 0;
+                }
+
+                self.debug_last_state = Some(state);
 
-                last_address = self.registers.pc;
+                let trap_hit =
+                    self.debug_trapping && self.debug_repeat_count >= self.trap_threshold.max(1);
 
-                if stepping || trap_hit || self.breakpoints.contains(&self.registers.pc) {
-                    stepping = false;
+                let breakpoint_hit = self.check_breakpoints();
+
+                if self.debug_steps_remaining > 0 {
+                    self.debug_steps_remaining -= 1;
+                    self.debug_stepping |= self.debug_steps_remaining == 0;
+                }
 
-                    let debug_display = "\r\n".to_string()
-                        + &self.registers.to_string()
-                        + "\r\n"
-                        + &self.disassemble_lines(self.registers.pc as usize, 8);
+                if self.debug_stepping || trap_hit || breakpoint_hit {
+                    self.debug_stepping = false;
+                    self.debug_steps_remaining = 0;
+
+                    let debug_display = match self.debugger_verbose {
+                        true => {
+                            "\r\n".to_string()
+                                + &self.registers.to_string()
+                                + &format!("CYC:{}\r\n", self.cycles)
+                                + "\r\n"
+                                + &self.disassemble_lines(self.registers.pc as usize, 8)
+                        }
+                        false => String::new(),
+                    };
 
                     let mut output = String::new();
 
@@ -153,1282 +649,5057 @@ impl Cpu {
                     }
 
                     loop {
-                        let input = debugger.unwrap()(&output).trim().to_uppercase();
-
-                        let split_input: Vec<&str> = input.split(" ").collect();
-
-                        match split_input[0] {
-                            "B" => {
-                                output = self.debug_toggle_breakpoint(split_input[1]);
-                                continue;
-                            },
-                            "D" => {
-                                output = self.debug_display_memory(split_input[1]);
-                                continue;
-                            },
-                            "Q" => return,
-                            "S" => {
-                                stepping = true;
+                        let input = debugger.unwrap()(&output);
+
+                        // An empty string, rather than an empty line, means the input has
+                        // been closed, so there's nobody left to answer the prompt.
+                        let command = match input.is_empty() {
+                            true => Ok(DebugCommand::Quit),
+                            false => parse_debug_command(&input),
+                        };
+
+                        if !matches!(command, Ok(DebugCommand::History)) {
+                            self.record_debug_history(&input);
+                        }
+
+                        match command {
+                            Ok(DebugCommand::ToggleBreakpoint(address)) => {
+                                output = self.debug_toggle_breakpoint(address);
+                            }
+                            Ok(DebugCommand::DisplayMemory { address, length }) => {
+                                output = self.debug_display_memory(address, length);
+                            }
+                            Ok(DebugCommand::DisplayStack) => {
+                                output = self.debug_display_stack();
+                            }
+                            Ok(DebugCommand::Pause) => {
+                                // Resuming shows the debugger again at the same instruction.
+                                self.debug_stepping = true;
+                                self.debug_last_state = None;
+
+                                return RunExit::Paused(self.registers.pc);
+                            }
+                            Ok(DebugCommand::Quit) => {
+                                self.debug_stepping = true;
+                                self.debug_trapping = true;
+                                self.debug_last_state = None;
+
+                                return RunExit::Quit;
+                            }
+                            Ok(DebugCommand::Step(count)) => {
+                                self.debug_steps_remaining = count;
                                 break;
-                            },
-                            "T" => {
-                                trap = !trap;
+                            }
+                            Ok(DebugCommand::ToggleTrapping) => {
+                                self.debug_trapping = !self.debug_trapping;
 
                                 output = format!(
                                     "Trapping is {}.",
-                                    match trap {
+                                    match self.debug_trapping {
+                                        true => "enabled",
+                                        false => "disabled",
+                                    }
+                                );
+                            }
+                            Ok(DebugCommand::ToggleVerbose) => {
+                                self.debugger_verbose = !self.debugger_verbose;
+
+                                output = format!(
+                                    "Verbose display is {}.",
+                                    match self.debugger_verbose {
                                         true => "enabled",
                                         false => "disabled",
                                     }
                                 );
-                                continue;
                             }
-                            "X" => break,
-                            "?" | "" => {
-                                output = "\r\n\
-                                    S - Step\r\n\
-                                    T - Toggle Trapping\
-                                    X - Execute\r\n\
-                                    Q - Quit\r\n\
-                                    ? - Help\r\n"
-                                    .to_string();
+                            Ok(DebugCommand::Execute) => break,
+                            Ok(DebugCommand::Help) => {
+                                output = Cpu::debugger_help();
                             }
-                            _ => {
-                                output = "Unrecognized command".to_string();
-                                continue;
+                            Ok(DebugCommand::History) => {
+                                output = self.debug_history();
+                            }
+                            Err(error) => {
+                                output = error.to_string();
                             }
                         }
                     }
                 }
             }
 
-            let instruction_start_time = Instant::now();
-
-            if let Some(execution_return_values) = self.execute_opcode() {
-                if !execution_return_values.set_program_counter {
-                    self.registers.pc += execution_return_values.bytes as u16;
-                }
-
-                let instruction_end_time = Instant::now();
-
-                let elapsed_time = instruction_end_time
-                    .duration_since(instruction_start_time)
-                    .as_secs_f64();
+            if self.halt_on_unhandled_brk && self.is_unhandled_brk() {
+                return RunExit::BreakWithoutHandler(self.registers.pc);
+            }
 
-                let target_time =
-                    self.cycle_duration * execution_return_values.clock_periods as f64;
+            let instruction_start_time = self.clock.now();
 
-                if target_time > elapsed_time {
-                    std::thread::sleep(Duration::from_secs_f64(target_time - elapsed_time));
-                }
-            } else {
-                panic!(
-                    "Unrecognized opcode: {:02X} @ {:04X}",
-                    self.memory.contents[self.registers.pc as usize], self.registers.pc
+            if let Some(execution_return_values) = self.execute_instruction() {
+                self.throttle(
+                    instruction_start_time,
+                    execution_return_values.clock_periods as u64,
                 );
+            } else {
+                return self.unexecuted_opcode_exit();
+            }
+
+            if self.exceeds_max_call_depth() {
+                return RunExit::RecursionLimit(self.registers.pc);
             }
         }
     }
 
-    pub fn disassemble_lines(&mut self, starting_address: usize, number_of_lines: u8) -> String {
-        let mut address = starting_address;
-        let mut result = String::new();
+    /// Runs the cpu as fast as possible without a debugger until an instruction traps,
+    /// which is how test suites such as Klaus Dormann's functional test report their
+    /// result. An instruction traps when it jumps or branches to itself without changing
+    /// any register.
+    ///
+    /// Returns `RunExit::Success` if the trap is at `success_address`, otherwise
+    /// `RunExit::Trapped` with the address of the trap. Returns `RunExit::BreakWithoutHandler`
+    /// if `halt_on_unhandled_brk` is set, `RunExit::Jammed` for a JAM opcode or `RunExit::Error`
+    /// for an unrecognized opcode.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    /// use rust_6502::cpu::run_exit::RunExit;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_000_000.0);
+    /// cpu.power_up();
+    ///
+    /// // LDX #$03, DEX, BNE $0402, JMP $0405
+    /// cpu.memory.save_u8_vector_into_memory(
+    ///     0x0400,
+    ///     vec![0xA2, 0x03, 0xCA, 0xD0, 0xFD, 0x4C, 0x05, 0x04],
+    /// );
+    ///
+    /// assert_eq!(cpu.run_headless(), RunExit::Trapped(0x0405));
+    /// assert_eq!(cpu.registers.x, 0x00);
+    /// ```
+    pub fn run_headless(&mut self) -> RunExit {
+        loop {
+            self.poll_interrupts();
 
-        for _ in 0..number_of_lines {
-            if let Some((line, length)) = self.disassemble_opcode(address) {
-                result.push_str(&line);
-                result.push_str("\r\n");
+            if self.halt_on_unhandled_brk && self.is_unhandled_brk() {
+                return RunExit::BreakWithoutHandler(self.registers.pc);
+            }
 
-                address += length as usize;
-            } else {
-                let bytes = format!("{:02X}", self.memory.contents[address]);
+            let state = self.trap_state();
 
-                let line = format!("{:04X} {:<9} UNRECONIZED OPCODE", address, bytes);
+            if self.execute_instruction().is_none() {
+                return self.unexecuted_opcode_exit();
+            }
 
-                result.push_str(&line);
-                result.push_str("\r\n");
+            if self.exceeds_max_call_depth() {
+                return RunExit::RecursionLimit(self.registers.pc);
+            }
+
+            if self.rdy && self.trap_state() == state {
+                if self.success_address == Some(self.registers.pc) {
+                    return RunExit::Success;
+                }
 
-                address += 1;
+                return RunExit::Trapped(self.registers.pc);
             }
         }
-
-        result
     }
 
-    pub fn disassemble_opcode(&self, address: usize) -> Option<(String, u8)> {
-        let instruction = self.get_instruction_for_opcode(address)?;
+    /// Runs the cpu one video frame at a time. Each frame executes whole instructions
+    /// until `frame_cycles` cycles have elapsed and then calls `on_frame`, which is
+    /// where a front-end renders, polls input or raises the VBlank NMI. Cycles that
+    /// overshoot a frame are taken from the next one and each frame is throttled to
+    /// the cpu's clock speed.
+    ///
+    /// # Arguments
+    /// * `frame_cycles` - The number of cycles in a frame.
+    /// * `frames` - The number of frames to run.
+    /// * `on_frame` - Called at the end of every frame.
+    ///
+    /// Returns a `CpuError` if an opcode isn't recognized.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_000_000.0);
+    /// cpu.power_up();
+    ///
+    /// // JMP $0400
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0x4C, 0x00, 0x04]);
+    ///
+    /// cpu.run_frames(100, 2, |cpu: &mut Cpu| cpu.nmi_triggered = true).unwrap();
+    ///
+    /// assert!(cpu.cycles() >= 200);
+    /// ```
+    pub fn run_frames(
+        &mut self,
+        frame_cycles: u64,
+        frames: u64,
+        mut on_frame: impl FnMut(&mut Cpu),
+    ) -> Result<(), CpuError> {
+        let mut frame_end = self.cycles;
 
-        let mut bytes = String::new();
+        for _ in 0..frames {
+            let frame_start_time = self.clock.now();
 
-        for i in 0..instruction.bytes {
-            bytes = format!(
-                "{} {:02X}",
-                bytes,
-                self.memory.get_8_bit_value(address + i as usize)
-            );
-        }
+            frame_end += frame_cycles;
 
-        let operand = match instruction.addressing_mode {
-            AddressingMode::Accumulator => "A".to_string(),
-            AddressingMode::Implied => String::new(),
-            AddressingMode::Relative => {
-                let offset = self.memory.get_8_bit_value(address + 1);
-                let relative_address =
-                    Cpu::calculate_address_from_relative_offset((address + 2) as u16, offset);
-                format!("${:04X}", relative_address)
-            }
-            AddressingMode::ZeroPage => {
-                format!("${:02X}", self.memory.get_8_bit_value(address + 1))
-            }
-            AddressingMode::Immediate => {
-                format!("#${:02X}", self.memory.get_8_bit_value(address + 1))
-            }
-            AddressingMode::ZeroPageX => {
-                format!("${:02X},X", self.memory.get_8_bit_value(address + 1))
-            }
-            AddressingMode::ZeroPageY => {
-                format!("${:02X},Y", self.memory.get_8_bit_value(address + 1))
-            }
-            AddressingMode::Absolute => {
-                format!("${:04X}", self.memory.get_16_bit_value(address + 1))
-            }
-            AddressingMode::AbsoluteX => {
-                format!("${:04X},X", self.memory.get_16_bit_value(address + 1))
-            }
-            AddressingMode::AbsoluteY => {
-                format!("${:04X},Y", self.memory.get_16_bit_value(address + 1))
-            }
-            AddressingMode::Indirect => {
-                format!(
-                    "(${:04X})",
-                    self.memory.get_16_bit_value(address + 1) as usize
-                )
-            }
-            AddressingMode::IndirectX => {
-                format!("(${:02X},X)", self.memory.get_8_bit_value(address + 1))
-            }
-            AddressingMode::IndirectY => {
-                format!("(${:02X}),Y", self.memory.get_8_bit_value(address + 1))
+            while self.cycles < frame_end {
+                self.poll_interrupts();
+
+                if self.execute_instruction().is_none() {
+                    return Err(self.unrecognized_opcode_error());
+                }
             }
-        };
 
-        let line = format!(
-            "{:04X} {:<9} {:<4} {}",
-            address, bytes, instruction.mnemonic, operand
-        );
+            self.throttle(frame_start_time, frame_cycles);
 
-        Some((line, instruction.bytes))
+            on_frame(self);
+        }
+
+        Ok(())
     }
 
-    /***********************************************************
-     *
-     * Private utility functions.
-     ***********************************************************/
+    /// Evaluates a snippet of code on a new cpu and returns the cpu in its final state.
+    ///
+    /// The code is loaded at `EVAL_ADDRESS` and runs until it reaches an RTS that would return
+    /// from the code, a trap (an instruction that jumps or branches to itself), a BRK without
+    /// a handler or an unrecognized opcode. Nothing is throttled.
+    ///
+    /// # Arguments
+    /// * `code` - The machine code to run.
+    /// * `setup` - A closure that prepares the registers or memory before the code runs.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// // LDA #$05, CLC, ADC #$03
+    /// let cpu = Cpu::eval(&[0xA9, 0x05, 0x18, 0x69, 0x03], |_| {});
+    ///
+    /// assert_eq!(cpu.registers.a, 0x08);
+    /// ```
+    pub fn eval(code: &[u8], setup: impl FnOnce(&mut Cpu)) -> Cpu {
+        let mut cpu = Cpu::new(EVAL_ADDRESS, 1_000_000.0);
+        cpu.power_up();
 
-    fn branch(&mut self, instruction: Instruction, pred: bool) -> ExecutionReturnValues {
-        if !pred {
-            self.registers.pc += instruction.bytes as u16;
-            return ExecutionReturnValues::new(instruction, false);
-        }
+        cpu.memory
+            .save_u8_vector_into_memory(EVAL_ADDRESS as usize, code.to_vec());
 
-        let old_pc = self.registers.pc;
+        setup(&mut cpu);
 
-        let offset = self.memory.contents[(self.registers.pc + 1) as usize];
+        let stack_pointer = cpu.registers.sp;
 
-        let relative_address =
-            Cpu::calculate_address_from_relative_offset(self.registers.pc + 2, offset);
+        loop {
+            let address = cpu.registers.pc;
 
-        self.registers.pc = relative_address;
+            let returning = cpu.memory.get_8_bit_value(address as usize) == RTS_OPCODE
+                && cpu.registers.sp == stack_pointer;
 
-        ExecutionReturnValues::new(
-            instruction,
-            Cpu::crosses_boundary_by_two_addresses(old_pc, relative_address),
-        )
-    }
+            if returning || cpu.is_unhandled_brk() {
+                break;
+            }
 
-    fn calculate_address_from_relative_offset(base_address: u16, offset: u8) -> u16 {
-        match offset & 0x80 {
-            0x80 => {
-                let positive_offset = !offset + 1;
-                base_address - positive_offset as u16
+            if cpu.execute_instruction().is_none() || cpu.registers.pc == address {
+                break;
             }
-            _ => base_address + offset as u16,
         }
+
+        cpu
     }
 
-    fn compare(&mut self, register_value: u8, value: u8) {
-        let result = (register_value as u16).wrapping_sub(value as u16);
+    /// Executes the next instruction, servicing a pending interrupt first. A `JSR` is stepped
+    /// over: the whole subroutine runs, servicing interrupts, until it returns to the
+    /// instruction after the `JSR`.
+    ///
+    /// Returns the values of the stepped instruction, which for a `JSR` don't include the
+    /// subroutine's cycles, `CpuError::Trapped` if the subroutine traps the way
+    /// `run_headless()` detects, or a `CpuError` if an opcode isn't recognized. A subroutine
+    /// that never returns without trapping, such as one that pulls its return address and
+    /// jumps into a longer loop, keeps `step_over()` running.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_000_000.0);
+    /// cpu.power_up();
+    ///
+    /// // JSR $0500, then LDA #$01 and RTS at $0500.
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0x20, 0x00, 0x05]);
+    /// cpu.memory.save_u8_vector_into_memory(0x0500, vec![0xA9, 0x01, 0x60]);
+    ///
+    /// cpu.step_over().unwrap();
+    ///
+    /// assert_eq!(cpu.registers.pc, 0x0403);
+    /// assert_eq!(cpu.registers.a, 0x01);
+    /// ```
+    pub fn step_over(&mut self) -> Result<ExecutionReturnValues, CpuError> {
+        self.poll_interrupts();
 
-        self.set_zero_flag(result as u8);
-        self.set_negative_flag(result as u8);
-        self.set_carry_flag(!result);
-    }
+        let is_jsr = self.current_opcode() == JSR_OPCODE;
+        let return_address = self.registers.pc.wrapping_add(3);
+        let stack_pointer = self.registers.sp;
 
-    fn crosses_boundary_by_address_offset(address: u16, offset: u8) -> bool {
-        address & 0xff00 != (address + offset as u16) & 0xff00
-    }
+        let return_values = self
+            .execute_instruction()
+            .ok_or_else(|| self.unrecognized_opcode_error())?;
 
-    fn crosses_boundary_by_two_addresses(base_address: u16, address: u16) -> bool {
-        base_address & 0xff00 != address & 0xff00
-    }
+        if is_jsr {
+            while self.registers.pc != return_address || self.registers.sp != stack_pointer {
+                self.poll_interrupts();
 
-    fn debug_display_memory(&mut self, address: &str) -> String {
-        if let Some(_) = self.hexadecimal_number_pattern.find(address) {
-            let address = usize::from_str_radix(
-                &IndexableStr::new(address)[1..],
-                16,
-            )
-            .unwrap();
+                let state = self.trap_state();
 
-            let mut output = format!("{:04X}:", address);
+                if self.execute_instruction().is_none() {
+                    return Err(self.unrecognized_opcode_error());
+                }
 
-            for i in 0..16 {
-                output = format!(
-                    "{} {:02X}",
-                    output,
-                    self.memory.contents[address + i as usize]
-                );
+                if self.rdy && self.trap_state() == state {
+                    return Err(CpuError::Trapped {
+                        address: self.registers.pc,
+                    });
+                }
             }
-
-            return output;
         }
 
-        "Display memory requires an valid hexadecimal address: D $FFE2.".to_string()
+        Ok(return_values)
     }
 
-    fn debug_toggle_breakpoint(&mut self, address: &str) -> String{
+    /// Runs until the current subroutine or interrupt handler returns, servicing interrupts,
+    /// and stops at the instruction after its `JSR` or the interrupted instruction. The
+    /// return is an `RTS` or `RTI` that leaves the stack pointer above where it was when
+    /// `step_out()` was called, so nested calls are run through.
+    ///
+    /// Returns `CpuError::NoReturnAddress` without executing anything if the stack pointer
+    /// is above $01FD, which leaves no room for a return address, or a `CpuError` if an
+    /// opcode isn't recognized.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_000_000.0);
+    /// cpu.power_up();
+    ///
+    /// // JSR $0500, then LDA #$01 and RTS at $0500.
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0x20, 0x00, 0x05]);
+    /// cpu.memory.save_u8_vector_into_memory(0x0500, vec![0xA9, 0x01, 0x60]);
+    ///
+    /// // Execute the JSR.
+    /// cpu.step_until_cycles(6).unwrap();
+    ///
+    /// cpu.step_out().unwrap();
+    ///
+    /// assert_eq!(cpu.registers.pc, 0x0403);
+    /// assert_eq!(cpu.registers.a, 0x01);
+    /// ```
+    pub fn step_out(&mut self) -> Result<(), CpuError> {
+        let stack_pointer = self.registers.sp;
+
+        // An RTS or RTI that raised the stack pointer above $01FF would wrap instead.
+        if stack_pointer > 0xFD {
+            return Err(CpuError::NoReturnAddress {
+                address: self.registers.pc,
+            });
+        }
+
+        loop {
+            self.poll_interrupts();
 
-        if let Some(_) = self.hexadecimal_number_pattern.find(address) {
-            let breakpoint = u16::from_str_radix(
-                &IndexableStr::new(address)[1..],
-                16,
-            )
-            .unwrap();
+            let returning = matches!(self.current_opcode(), RTS_OPCODE | RTI_OPCODE);
 
-            if let Some(index) =
-                self.breakpoints.iter().position(|&x| x == breakpoint)
-            {
-                self.breakpoints.remove(index);
-                return format!("Breakpoint removed @ {:04X}", breakpoint);
+            if self.execute_instruction().is_none() {
+                return Err(self.unrecognized_opcode_error());
             }
 
-            self.breakpoints.push(breakpoint);
-            return format!("Breakpoint added @ {:04X}", breakpoint);
+            if returning && self.registers.sp > stack_pointer {
+                return Ok(());
+            }
         }
-        
-        "Breakpoint requires an valid hexadecimal address: B $FFE2."
-                .to_string()
     }
 
-    fn get_address(&self, instruction: Instruction) -> (usize, bool) {
-        match instruction.addressing_mode {
-            AddressingMode::Accumulator => {
-                panic!("Can't get an address for the Accumulator addressing mode.")
+    /// Executes the next instruction without throttling, so a front-end's own main loop
+    /// can drive the cpu with a cycle budget. Pending interrupts aren't serviced.
+    ///
+    /// Returns the cycles the instruction took, including the page crossing and branch
+    /// penalties, or `None` if the opcode isn't recognized.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_000_000.0);
+    /// cpu.power_up();
+    ///
+    /// // LDA #$01
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0xA9, 0x01]);
+    ///
+    /// assert_eq!(cpu.step(), Some(2));
+    /// assert_eq!(cpu.registers.pc, 0x0402);
+    /// ```
+    pub fn step(&mut self) -> Option<u8> {
+        self.execute_instruction()
+            .map(|execution_return_values| execution_return_values.clock_periods)
+    }
+
+    /// Executes whole instructions, servicing interrupts, until the cycle counter reaches
+    /// or exceeds a target. Nothing is throttled.
+    ///
+    /// Returns the cycle count reached, which overshoots the target by up to the cost of
+    /// the last instruction, or a `CpuError` if an opcode isn't recognized.
+    ///
+    /// # Arguments
+    /// * `target_total_cycles` - The cycle count to reach.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_000_000.0);
+    /// cpu.power_up();
+    ///
+    /// // JMP $0400
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0x4C, 0x00, 0x04]);
+    ///
+    /// assert_eq!(cpu.step_until_cycles(10), Ok(12));
+    /// ```
+    pub fn step_until_cycles(&mut self, target_total_cycles: u64) -> Result<u64, CpuError> {
+        while self.cycles < target_total_cycles {
+            self.poll_interrupts();
+
+            if self.cycles >= target_total_cycles {
+                break;
             }
-            AddressingMode::Implied => {
-                panic!("Can't get an address for the Implied addressing mode.")
+
+            if self.execute_instruction().is_none() {
+                return Err(self.unrecognized_opcode_error());
             }
-            AddressingMode::Relative => {
-                panic!("Can't get an address for the Relative addressing mode.")
+        }
+
+        Ok(self.cycles)
+    }
+
+    /// Adds a peripheral that is ticked with the cycles of every instruction and interrupt.
+    ///
+    /// # Arguments
+    /// * `peripheral` - The peripheral to add.
+    pub fn add_peripheral(&mut self, peripheral: Box<dyn Peripheral>) {
+        self.peripherals.push(peripheral);
+    }
+
+    /// Sets a callback that is called with a `TraceRecord` before every instruction executes.
+    ///
+    /// # Arguments
+    /// * `callback` - The closure to call.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.power_up();
+    ///
+    /// let trace = Rc::new(RefCell::new(Vec::new()));
+    /// let records = trace.clone();
+    ///
+    /// cpu.set_trace_callback(move |record| records.borrow_mut().push(record.pc));
+    ///
+    /// // Save a NOP instruction at 0x0400.
+    /// cpu.memory.contents[0x0400] = 0xEA;
+    ///
+    /// cpu.execute_opcode();
+    ///
+    /// assert_eq!(*trace.borrow(), vec![0x0400]);
+    /// ```
+    pub fn set_trace_callback(&mut self, callback: impl FnMut(&TraceRecord) + 'static) {
+        self.trace_callback = Some(Box::new(callback));
+    }
+
+    /// Sets a hook that is called with the decoded instruction just before each instruction
+    /// executed by `Cpu::run()` and the other stepping methods.
+    ///
+    /// # Arguments
+    /// * `hook` - The closure to call.
+    pub fn set_pre_exec_hook(&mut self, hook: impl FnMut(&mut Cpu, &DecodedInstruction) + 'static) {
+        self.pre_exec_hook = Some(Box::new(hook));
+    }
+
+    /// Sets a hook that is called with the decoded instruction just after each instruction
+    /// executed by `Cpu::run()` and the other stepping methods.
+    ///
+    /// # Arguments
+    /// * `hook` - The closure to call.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// // LDA #$05
+    /// let cpu = Cpu::eval(&[0xA9, 0x05], |cpu| {
+    ///     cpu.set_post_exec_hook(|cpu, _| cpu.registers.a += 1);
+    /// });
+    ///
+    /// assert_eq!(cpu.registers.a, 0x06);
+    /// ```
+    pub fn set_post_exec_hook(&mut self, hook: impl FnMut(&mut Cpu, &DecodedInstruction) + 'static) {
+        self.post_exec_hook = Some(Box::new(hook));
+    }
+
+    /// Removes the pre and post execution hooks.
+    pub fn clear_exec_hooks(&mut self) {
+        self.pre_exec_hook = None;
+        self.post_exec_hook = None;
+    }
+
+    /// Stops execution in the debugger after any instruction that changes a flag. The
+    /// flag is compared to its previous value after each instruction, next to the
+    /// post-exec hook, so a hook set by `Cpu::set_post_exec_hook()` still runs.
+    ///
+    /// # Arguments
+    /// * `flag` - The flag to watch.
+    pub fn break_on_flag_change(&mut self, flag: Flag) {
+        self.flag_watches.push((flag, self.registers.p.is_set(flag)));
+    }
+
+    /// Limits tracing to instructions whose address is within a range.
+    ///
+    /// # Arguments
+    /// * `range` - The addresses to trace.
+    pub fn set_trace_filter(&mut self, range: RangeInclusive<u16>) {
+        self.trace_filter = Some(range);
+    }
+
+    /// Removes the trace filter so that every instruction is traced again.
+    pub fn clear_trace_filter(&mut self) {
+        self.trace_filter = None;
+    }
+
+    /// Returns `true` if a flag in the status register is set.
+    ///
+    /// # Arguments
+    /// * `flag` - The flag to check.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::status_flags::Flag;
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.power_up();
+    ///
+    /// assert!(cpu.is_flag_set(Flag::InterruptDisable));
+    /// assert!(!cpu.is_flag_set(Flag::Carry));
+    /// ```
+    pub fn is_flag_set(&self, flag: Flag) -> bool {
+        self.registers.p.is_set(flag)
+    }
+
+    /// Writes a 16-bit value to memory in little-endian order: the least significant
+    /// byte at `address` and the most significant byte at `address + 1`, wrapping at $FFFF.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the least significant byte.
+    /// * `value` - The value to write.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// cpu.poke_u16(0x00FB, 0xC000);
+    ///
+    /// assert_eq!(cpu.memory.contents[0x00FB], 0x00);
+    /// assert_eq!(cpu.memory.contents[0x00FC], 0xC0);
+    /// ```
+    pub fn poke_u16(&mut self, address: u16, value: u16) {
+        self.memory.set_8_bit_value(address as usize, value as u8);
+        self.memory
+            .set_8_bit_value(address.wrapping_add(1) as usize, (value >> 8) as u8);
+    }
+
+    /// Writes a batch of address and value pairs to memory.
+    ///
+    /// # Arguments
+    /// * `entries` - The addresses and the values to write to them.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// // LDA #$FF
+    /// cpu.poke_many(&[(0x0400, 0xA9), (0x0401, 0xFF)]);
+    ///
+    /// assert_eq!(cpu.memory.contents[0x0401], 0xFF);
+    /// ```
+    pub fn poke_many(&mut self, entries: &[(u16, u8)]) {
+        for (address, value) in entries {
+            self.memory.set_8_bit_value(*address as usize, *value);
+        }
+    }
+
+    /// Reads a little-endian 16-bit value from memory, wrapping at $FFFF.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the least significant byte.
+    pub fn peek_u16(&self, address: u16) -> u16 {
+        let lsb = self.memory.get_8_bit_value(address as usize);
+        let msb = self.memory.get_8_bit_value(address.wrapping_add(1) as usize);
+
+        (msb as u16) << 8 | lsb as u16
+    }
+
+    /// Panics with every mismatched address if memory starting at `start` doesn't match
+    /// `expected`. Intended for tests.
+    ///
+    /// # Arguments
+    /// * `start` - The address of the first byte to check.
+    /// * `expected` - The bytes memory should contain.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// cpu.poke_many(&[(0x0400, 0xA9), (0x0401, 0xFF)]);
+    ///
+    /// cpu.assert_memory(0x0400, &[0xA9, 0xFF]);
+    /// ```
+    pub fn assert_memory(&self, start: u16, expected: &[u8]) {
+        let mut mismatches = String::new();
+
+        for (i, expected_value) in expected.iter().enumerate() {
+            let address = start.wrapping_add(i as u16);
+            let actual_value = self.memory.contents[address as usize];
+
+            if actual_value != *expected_value {
+                mismatches = format!(
+                    "{}\n  {:04X}: expected {:02X}, found {:02X}",
+                    mismatches, address, expected_value, actual_value
+                );
             }
-            AddressingMode::Immediate => (self.registers.pc as usize + 1, false),
-            AddressingMode::ZeroPage => {
-                let zero_page_offset = self
-                    .memory
-                    .get_8_bit_value((self.registers.pc + 1) as usize);
+        }
 
-                (zero_page_offset as usize, false)
+        if !mismatches.is_empty() {
+            panic!("Memory starting at {:04X} doesn't match:{}", start, mismatches);
+        }
+    }
+
+    /// Returns a borrowed view of a range of memory. Read hooks are bypassed.
+    ///
+    /// # Arguments
+    /// * `range` - The addresses to view.
+    pub fn memory_view(&self, range: RangeInclusive<u16>) -> &[u8] {
+        self.memory.slice(*range.start(), *range.end())
+    }
+
+    /// Replaces the clock used to throttle execution to the cpu's clock speed, which is the
+    /// real time by default. A `MockClock` lets tests check the timing without sleeping.
+    ///
+    /// # Arguments
+    /// * `clock` - The new clock.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::clock::MockClock;
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_000.0);
+    /// cpu.power_up();
+    ///
+    /// let clock = MockClock::new();
+    /// cpu.set_clock(clock.clone());
+    ///
+    /// // JMP $0400
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0x4C, 0x00, 0x04]);
+    ///
+    /// // Two frames of 3 cycles take 6ms at 1KHz.
+    /// cpu.run_frames(3, 2, |_: &mut Cpu| {}).unwrap();
+    ///
+    /// assert_eq!(clock.elapsed().as_millis(), 6);
+    /// ```
+    pub fn set_clock(&mut self, clock: impl Clock + 'static) {
+        self.clock = Box::new(clock);
+    }
+
+    /// Sets the RDY line. While it's low the cpu is stalled, as it is during DMA: each step
+    /// of `Cpu::run()` or `Cpu::run_frames()` consumes one cycle and ticks the peripherals
+    /// without executing an instruction or servicing an interrupt.
+    ///
+    /// # Arguments
+    /// * `ready` - `false` pulls RDY low and `true` releases it.
+    pub fn set_rdy(&mut self, ready: bool) {
+        self.rdy = ready;
+
+        self.record_input(Input::Rdy(ready));
+    }
+
+    /// Starts recording the inputs that come from outside the cpu, so the execution that
+    /// follows can be reproduced exactly with `Cpu::replay()`. The NMI and IRQ lines are
+    /// recorded as the cpu polls them at instruction boundaries and RDY as `Cpu::set_rdy()`
+    /// is called. Values supplied by memory hooks and peripherals aren't recorded.
+    ///
+    /// Returns a snapshot of the state the recording starts from.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_000_000.0);
+    /// cpu.power_up();
+    ///
+    /// // JMP $0400
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0x4C, 0x00, 0x04]);
+    ///
+    /// let start = cpu.record();
+    ///
+    /// cpu.step_until_cycles(30).unwrap();
+    /// cpu.nmi_triggered = true;
+    /// cpu.step_until_cycles(60).unwrap();
+    ///
+    /// let log = cpu.stop_recording().unwrap();
+    /// let end = cpu.snapshot();
+    ///
+    /// cpu.replay(&start, log);
+    /// cpu.step_until_cycles(60).unwrap();
+    ///
+    /// assert_eq!(cpu.snapshot(), end);
+    /// ```
+    pub fn record(&mut self) -> CpuSnapshot {
+        let mut log = InputLog::default();
+
+        if !self.rdy {
+            log.events.push(InputEvent {
+                cycles: self.cycles,
+                input: Input::Rdy(false),
+            });
+        }
+
+        self.recording = Some(Recording {
+            log,
+            lines: (self.nmi_triggered, self.irq_triggered),
+        });
+
+        self.snapshot()
+    }
+
+    /// Stops recording and returns the recorded inputs, or `None` if `Cpu::record()`
+    /// wasn't called.
+    pub fn stop_recording(&mut self) -> Option<InputLog> {
+        self.recording.take().map(|recording| recording.log)
+    }
+
+    /// Restores the snapshot returned by `Cpu::record()` and replays the recorded inputs
+    /// as execution reaches the cycle counts they were recorded at.
+    ///
+    /// # Arguments
+    /// * `snapshot` - The snapshot the recording started from.
+    /// * `log` - The inputs returned by `Cpu::stop_recording()`.
+    pub fn replay(&mut self, snapshot: &CpuSnapshot, log: InputLog) {
+        self.restore(snapshot);
+
+        self.rdy = true;
+        self.skip_next_irq = false;
+        self.replaying = Some(Replaying { log, next: 0 });
+    }
+
+    /// Returns the number of cycles executed since the cpu was created or `Cpu::reset_cycles()` was called.
+    pub fn cycles(&self) -> u64 {
+        self.cycles
+    }
+
+    /// Resets the cycle counter to zero.
+    pub fn reset_cycles(&mut self) {
+        self.cycles = 0;
+    }
+
+    /// Returns the number of instructions executed since the cpu was created or
+    /// `Cpu::reset_total_instructions()` was called. Interrupts aren't counted.
+    pub fn total_instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    /// Resets the instruction counter to zero.
+    pub fn reset_total_instructions(&mut self) {
+        self.instructions = 0;
+    }
+
+    /// Returns the number of `JSR`s, `BRK`s and interrupts that haven't returned yet with an
+    /// `RTS` or `RTI`. It's reset by `Cpu::power_up()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.power_up();
+    ///
+    /// // JSR $0410
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0x20, 0x10, 0x04]);
+    /// cpu.step_until_cycles(6).unwrap();
+    ///
+    /// assert_eq!(cpu.call_depth(), 1);
+    /// ```
+    pub fn call_depth(&self) -> u32 {
+        self.call_depth
+    }
+
+    /// Sets a breakpoint at an address or removes it if one is already set.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the breakpoint.
+    ///
+    /// Returns `true` if the breakpoint is now set, otherwise `false`.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// assert!(cpu.toggle_breakpoint(0x0410));
+    /// assert!(!cpu.toggle_breakpoint(0x0410));
+    /// ```
+    pub fn toggle_breakpoint(&mut self, address: u16) -> bool {
+        if let Some(index) = self.breakpoints.iter().position(|&x| x == address) {
+            self.breakpoints.remove(index);
+            return false;
+        }
+
+        self.breakpoints.push(address);
+
+        true
+    }
+
+    /// Sets the breakpoints listed in a file, which has one `$XXXX` address per line. Anything
+    /// after a `#` is a comment. Addresses that already have a breakpoint are left set.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the file to read.
+    ///
+    /// Returns the number of addresses in the file.
+    pub fn load_breakpoints(&mut self, file_path: &str) -> std::io::Result<usize> {
+        let text = std::fs::read_to_string(file_path)?;
+
+        let mut addresses = Vec::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+
+            if line.is_empty() {
+                continue;
             }
-            AddressingMode::ZeroPageX => {
-                let zero_page_offset = self
-                    .memory
-                    .get_8_bit_value((self.registers.pc + 1) as usize);
 
-                (
-                    (zero_page_offset as usize + self.registers.x as usize) & 0x00FF,
-                    false,
-                )
+            let address = line
+                .strip_prefix('$')
+                .and_then(|hexadecimal| u16::from_str_radix(hexadecimal, 16).ok())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("Line {} isn't a $XXXX address: {}", index + 1, line),
+                    )
+                })?;
+
+            addresses.push(address);
+        }
+
+        for &address in &addresses {
+            if !self.breakpoints.contains(&address) {
+                self.breakpoints.push(address);
             }
-            AddressingMode::ZeroPageY => {
-                let zero_page_offset = self
-                    .memory
-                    .get_8_bit_value((self.registers.pc + 1) as usize);
+        }
 
-                (
-                    (zero_page_offset as usize + self.registers.y as usize) & 0x00FF,
-                    false,
-                )
+        Ok(addresses.len())
+    }
+
+    /// Saves the breakpoints to a file that `Cpu::load_breakpoints()` can read.
+    ///
+    /// # Arguments
+    /// * `file_path` - The path of the file to write.
+    pub fn save_breakpoints(&self, file_path: &str) -> std::io::Result<()> {
+        let text: String = self
+            .breakpoints
+            .iter()
+            .map(|address| format!("${:04X}\n", address))
+            .collect();
+
+        std::fs::write(file_path, text)
+    }
+
+    /// Adds a breakpoint that stops execution in the debugger when `condition` returns `true`.
+    ///
+    /// # Arguments
+    /// * `address` - Only check the condition at this address. Pass `None` to check it before every instruction.
+    /// * `condition` - The condition to check.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// cpu.add_conditional_breakpoint(Some(0x0410), |cpu: &Cpu| cpu.registers.a == 0xFF);
+    /// ```
+    pub fn add_conditional_breakpoint(
+        &mut self,
+        address: Option<u16>,
+        condition: impl Fn(&Cpu) -> bool + 'static,
+    ) {
+        self.conditional_breakpoints.push(ConditionalBreakpoint {
+            address,
+            condition: Box::new(condition),
+            hit_count: 0,
+        });
+    }
+
+    /// Adds a conditional breakpoint that stops execution in the debugger when a register
+    /// holds a value, such as when the result of a calculation appears in A.
+    ///
+    /// # Arguments
+    /// * `register` - The register to check before every instruction.
+    /// * `value` - The value to stop at.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::registers::Register;
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// cpu.break_when_register(Register::A, 0xFF);
+    /// ```
+    pub fn break_when_register(&mut self, register: Register, value: u8) {
+        self.add_conditional_breakpoint(None, move |cpu: &Cpu| {
+            cpu.registers.value(register) == value
+        });
+    }
+
+    /// Adds a watchpoint that stops execution in the debugger before an instruction
+    /// reads or writes `address`.
+    ///
+    /// # Arguments
+    /// * `address` - The address to watch.
+    /// * `kind` - Whether to watch reads or writes.
+    pub fn add_watchpoint(&mut self, address: u16, kind: WatchKind) {
+        self.watchpoints.push(Watchpoint {
+            address,
+            kind,
+            hit_count: 0,
+        });
+    }
+
+    /// Returns the address and conditional breakpoints along with their hit counts.
+    pub fn list_breakpoints(&self) -> Vec<BreakpointInfo> {
+        let mut result: Vec<BreakpointInfo> = self
+            .breakpoints
+            .iter()
+            .map(|&address| BreakpointInfo {
+                address: Some(address),
+                kind: BreakpointKind::Address,
+                hit_count: *self.breakpoint_hits.get(&address).unwrap_or(&0),
+            })
+            .collect();
+
+        for breakpoint in &self.conditional_breakpoints {
+            result.push(BreakpointInfo {
+                address: breakpoint.address,
+                kind: BreakpointKind::Conditional,
+                hit_count: breakpoint.hit_count,
+            });
+        }
+
+        result
+    }
+
+    /// Returns the watchpoints along with their hit counts.
+    pub fn list_watchpoints(&self) -> Vec<BreakpointInfo> {
+        self.watchpoints
+            .iter()
+            .map(|watchpoint| BreakpointInfo {
+                address: Some(watchpoint.address),
+                kind: BreakpointKind::Watchpoint(watchpoint.kind),
+                hit_count: watchpoint.hit_count,
+            })
+            .collect()
+    }
+
+    /// Disassembles a number of lines starting at an address.
+    ///
+    /// Addresses wrap at `$FFFF`. An instruction whose operand would be read past `$FFFF`
+    /// is shown as a `.BYTE` line of the bytes up to `$FFFF`.
+    ///
+    /// # Arguments
+    /// * `starting_address` - The address of the first opcode.
+    /// * `number_of_lines` - The number of lines to disassemble.
+    pub fn disassemble_lines(&mut self, starting_address: usize, number_of_lines: u8) -> String {
+        let mut address = starting_address & 0xFFFF;
+        let mut result = String::new();
+
+        for _ in 0..number_of_lines {
+            if let Some((line, length)) = self.disassemble_opcode(address) {
+                result.push_str(&line);
+                result.push_str("\r\n");
+
+                address = (address + length as usize) & 0xFFFF;
+            } else if self.get_instruction_for_opcode(address).is_some() {
+                let bytes: Vec<u8> = (address..=0xFFFF)
+                    .map(|byte_address| self.memory.get_8_bit_value(byte_address))
+                    .collect();
+
+                result.push_str(&format_byte_line(address as u16, &bytes));
+                result.push_str("\r\n");
+
+                address = 0x0000;
+            } else {
+                let line = format_unrecognized_line(address as u16, self.memory.contents[address]);
+
+                result.push_str(&line);
+                result.push_str("\r\n");
+
+                address = (address + 1) & 0xFFFF;
             }
-            AddressingMode::Absolute => {
-                let address = self
-                    .memory
-                    .get_16_bit_value((self.registers.pc + 1) as usize);
+        }
+
+        result
+    }
+
+    /// Returns the opcode at the program counter.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.power_up();
+    ///
+    /// // Save a NOP instruction at 0x0400.
+    /// cpu.memory.contents[0x0400] = 0xEA;
+    ///
+    /// assert_eq!(cpu.current_opcode(), 0xEA);
+    /// ```
+    pub fn current_opcode(&self) -> u8 {
+        self.memory.get_8_bit_value(self.registers.pc as usize)
+    }
+
+    /// Disassembles the instruction at the program counter.
+    ///
+    /// Returns `None` if the opcode isn't recognized.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.power_up();
+    ///
+    /// // Save a LDA #$FF instruction at 0x0400.
+    /// cpu.memory.contents[0x0400] = 0xA9;
+    /// cpu.memory.contents[0x0401] = 0xFF;
+    ///
+    /// assert_eq!(cpu.disassemble_current().unwrap(), "0400  A9 FF    LDA  #$FF");
+    /// ```
+    pub fn disassemble_current(&self) -> Option<String> {
+        let (line, _) = self.disassemble_opcode(self.registers.pc as usize)?;
+
+        Some(line)
+    }
+
+    /// Estimates the cycles a straight-line routine takes without executing it, by adding
+    /// up the base cycle counts of the instructions from `start` to `end`. Branches taken
+    /// and page crossings depend on the data, so they aren't counted and the result is the
+    /// minimum.
+    ///
+    /// Returns `None` if an opcode in the range isn't recognized.
+    ///
+    /// # Arguments
+    /// * `start` - The address of the first instruction.
+    /// * `end` - The last address of the routine. The instruction starting at or before
+    ///   it is the last one counted.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// // LDA #$00, STA $D020
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0xA9, 0x00, 0x8D, 0x20, 0xD0]);
+    ///
+    /// assert_eq!(cpu.total_cycles_for_program(0x0400, 0x0404), Some(6));
+    /// ```
+    pub fn total_cycles_for_program(&self, start: u16, end: u16) -> Option<u64> {
+        let mut address = start as usize;
+        let mut cycles = 0;
+
+        while address <= end as usize {
+            let instruction = self.get_instruction_for_opcode(address)?;
+
+            cycles += instruction.clock_periods as u64;
+            address += instruction.bytes as usize;
+        }
+
+        Some(cycles)
+    }
+
+    /// Exports the code from `start` to `end` inclusive as a ca65 source listing that
+    /// reassembles to the same bytes. Branch, `JMP` and `JSR` targets within the range get
+    /// `Lxxxx` labels. Undocumented opcodes, unrecognized opcodes and an instruction cut off
+    /// by `end` are exported as `.byte` lines.
+    ///
+    /// # Arguments
+    /// * `start` - The address of the first instruction.
+    /// * `end` - The last address to export.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    ///
+    /// // LDA #$01, BNE $0400
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0xA9, 0x01, 0xD0, 0xFC]);
+    ///
+    /// assert_eq!(
+    ///     cpu.export_source(0x0400, 0x0403),
+    ///     "        .org $0400\n\nL0400:  lda #$01\n        bne L0400\n"
+    /// );
+    /// ```
+    pub fn export_source(&self, start: u16, end: u16) -> String {
+        let mut lines = Vec::new();
+        let mut address = start as usize;
+
+        while address <= end as usize {
+            match self.get_instruction_for_opcode(address) {
+                Some(instruction) if address + instruction.bytes as usize <= end as usize + 1 => {
+                    match instruction.is_documented {
+                        true => lines.push((address as u16, Some(instruction))),
+                        false => lines.extend(
+                            (0..instruction.bytes as u16).map(|i| (address as u16 + i, None)),
+                        ),
+                    }
+
+                    address += instruction.bytes as usize;
+                }
+                _ => {
+                    lines.push((address as u16, None));
+                    address += 1;
+                }
+            }
+        }
+
+        let instruction_addresses: Vec<u16> = lines
+            .iter()
+            .filter(|(_, instruction)| instruction.is_some())
+            .map(|(address, _)| *address)
+            .collect();
+
+        let labels: Vec<u16> = lines
+            .iter()
+            .filter_map(|(address, instruction)| self.jump_target(*address, (*instruction)?))
+            .filter(|target| instruction_addresses.contains(target))
+            .collect();
+
+        let mut source = String::new();
+
+        if self.variant == CpuVariant::Wdc65C02 {
+            source.push_str("        .setcpu \"65C02\"\n");
+        }
+
+        source.push_str(&format!("        .org ${:04x}\n\n", start));
+
+        let mut bytes: Vec<String> = Vec::new();
+
+        for (index, (address, instruction)) in lines.iter().enumerate() {
+            let Some(instruction) = instruction else {
+                bytes.push(format!("${:02x}", self.memory.get_8_bit_value(*address as usize)));
+
+                // Up to 8 bytes are exported on each .byte line.
+                if bytes.len() == 8 || !matches!(lines.get(index + 1), Some((_, None))) {
+                    source.push_str(&format!("        .byte {}\n", bytes.join(", ")));
+                    bytes.clear();
+                }
+
+                continue;
+            };
+
+            let label = match labels.contains(address) {
+                true => format!("L{:04X}:", address),
+                false => String::new(),
+            };
+
+            let operand = match self.jump_target(*address, *instruction) {
+                Some(target) if labels.contains(&target) => format!("L{:04X}", target),
+                _ => self.ca65_operand(*address, *instruction),
+            };
+
+            let line = format!("{:<8}{} {}", label, instruction.mnemonic.to_lowercase(), operand);
+
+            source.push_str(line.trim_end());
+            source.push('\n');
+        }
+
+        source
+    }
+
+    /// Disassembles the instruction at an address like `disassemble_opcode()` and, for
+    /// instructions that read or write memory, appends the effective address and its value
+    /// using the current registers, e.g. `LDA ($10),Y  @ $4002 = $55`. The address is only
+    /// shown for indexed and indirect modes. The value is read from `contents`, so read
+    /// hooks aren't called.
+    ///
+    /// Returns `None` if the opcode isn't recognized.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the instruction.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.registers.x = 0x02;
+    /// cpu.memory.contents[0x1236] = 0x55;
+    ///
+    /// // LDA $1234,X
+    /// cpu.memory.save_u8_vector_into_memory(0x0400, vec![0xBD, 0x34, 0x12]);
+    ///
+    /// assert_eq!(
+    ///     cpu.disassemble_with_context(0x0400).unwrap(),
+    ///     "0400  BD 34 12 LDA  $1234,X  @ $1236 = $55"
+    /// );
+    /// ```
+    pub fn disassemble_with_context(&self, address: usize) -> Option<String> {
+        let instruction = self.get_instruction_for_opcode(address)?;
+        let decoded = self.decode_instruction(address)?;
+
+        if !instruction.reads_memory() && !instruction.writes_memory() {
+            return Some(decoded.to_string());
+        }
+
+        let zero_page = decoded.bytes[1];
+        let absolute = || (decoded.bytes[2] as u16) << 8 | decoded.bytes[1] as u16;
+
+        let (effective_address, indexed) = match instruction.addressing_mode {
+            AddressingMode::ZeroPage => (zero_page as u16, false),
+            AddressingMode::ZeroPageX => (zero_page.wrapping_add(self.registers.x) as u16, true),
+            AddressingMode::ZeroPageY => (zero_page.wrapping_add(self.registers.y) as u16, true),
+            AddressingMode::Absolute => (absolute(), false),
+            AddressingMode::AbsoluteX => (absolute().wrapping_add(self.registers.x as u16), true),
+            AddressingMode::AbsoluteY => (absolute().wrapping_add(self.registers.y as u16), true),
+            AddressingMode::IndirectX => (
+                self.memory
+                    .zp_read_u16(zero_page.wrapping_add(self.registers.x)),
+                true,
+            ),
+            AddressingMode::IndirectY => (
+                self.memory
+                    .zp_read_u16(zero_page)
+                    .wrapping_add(self.registers.y as u16),
+                true,
+            ),
+            _ => return Some(decoded.to_string()),
+        };
+
+        let value = self.memory.contents[effective_address as usize];
+
+        Some(match indexed {
+            true => format!("{}  @ ${:04X} = ${:02X}", decoded, effective_address, value),
+            false => format!("{}  = ${:02X}", decoded, value),
+        })
+    }
+
+    pub fn disassemble_opcode(&self, address: usize) -> Option<(String, u8)> {
+        let decoded = self.decode_instruction(address)?;
+
+        Some((decoded.to_string(), decoded.bytes.len() as u8))
+    }
+
+    /// Decodes the instruction at an address.
+    ///
+    /// Returns `None` if the opcode isn't recognized or its operand would be read past `$FFFF`.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the opcode.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::Cpu;
+    ///
+    /// let mut cpu = Cpu::new(0x0400, 1_789_773.0);
+    /// cpu.power_up();
+    ///
+    /// // Save a BNE $0400 instruction at 0x0410.
+    /// cpu.memory.contents[0x0410] = 0xD0;
+    /// cpu.memory.contents[0x0411] = 0xEE;
+    ///
+    /// let decoded = cpu.decode_instruction(0x0410).unwrap();
+    ///
+    /// assert_eq!(decoded.relative_offset, Some(-18));
+    /// assert_eq!(decoded.operand_with_offset(), "$0400 (-18)");
+    /// ```
+    pub fn decode_instruction(&self, address: usize) -> Option<DecodedInstruction> {
+        let instruction = self.get_instruction_for_opcode(address)?;
+
+        if address + instruction.bytes as usize > 0x10000 {
+            return None;
+        }
+
+        let bytes: Vec<u8> = (0..instruction.bytes as usize)
+            .map(|i| self.memory.get_8_bit_value(address + i))
+            .collect();
+
+        Some(DecodedInstruction::new(&instruction, address as u16, bytes))
+    }
+
+    /***********************************************************
+     *
+     * Private utility functions.
+     ***********************************************************/
+
+    fn add_with_carry(&mut self, value: u8) {
+        if self.is_decimal_mode() {
+            return self.add_with_carry_decimal(value);
+        }
+
+        let carry = match self.registers.p.carry_flag {
+            true => 1u16,
+            false => 0u16,
+        };
+
+        let result = self.registers.a as u16 + value as u16 + carry;
+
+        self.set_zero_flag(result as u8);
+        self.set_negative_flag(result as u8);
+        self.set_overflow_flag(self.registers.a, value, result as u8);
+        self.set_carry_flag(result);
+
+        self.registers.a = result as u8;
+    }
+
+    // The NMOS 6502 sets Z from the binary sum and N and V from the sum after only the low
+    // nibble has been corrected. Only A and C get the fully corrected BCD result. The 65C02
+    // sets N and Z from the corrected result. See http://www.6502.org/tutorials/decimal_mode.html.
+    fn add_with_carry_decimal(&mut self, value: u8) {
+        let carry = match self.registers.p.carry_flag {
+            true => 1u16,
+            false => 0u16,
+        };
+
+        let a = self.registers.a as u16;
+        let binary_result = a + value as u16 + carry;
+
+        let mut low_nibble = (a & 0x0F) + (value as u16 & 0x0F) + carry;
+
+        if low_nibble >= 0x0A {
+            low_nibble = ((low_nibble + 0x06) & 0x0F) + 0x10;
+        }
+
+        let mut result = (a & 0xF0) + (value as u16 & 0xF0) + low_nibble;
+
+        self.set_negative_flag(result as u8);
+        self.set_overflow_flag(self.registers.a, value, result as u8);
+        self.set_zero_flag(binary_result as u8);
+
+        if result >= 0xA0 {
+            result += 0x60;
+        }
+
+        if self.variant == CpuVariant::Wdc65C02 {
+            self.set_negative_flag(result as u8);
+            self.set_zero_flag(result as u8);
+        }
+
+        self.set_carry_flag(result);
+
+        self.registers.a = result as u8;
+    }
+
+    fn branch(&mut self, instruction: Instruction, pred: bool) -> ExecutionReturnValues {
+        if !pred {
+            self.registers.pc = self.registers.pc.wrapping_add(instruction.bytes as u16);
+            return ExecutionReturnValues::new(instruction, false);
+        }
+
+        // Both the operand and the target wrap around the top of memory.
+        let next_address = self.registers.pc.wrapping_add(2);
+
+        let offset = self.memory.contents[self.registers.pc.wrapping_add(1) as usize];
+
+        let relative_address =
+            Cpu::calculate_address_from_relative_offset(next_address, offset);
+
+        self.registers.pc = relative_address;
+
+        // A taken branch always costs one extra cycle. It costs another one
+        // if the target is on a different page than the next instruction.
+        let mut execution_return_values = ExecutionReturnValues::new(
+            instruction,
+            Cpu::crosses_boundary_by_two_addresses(next_address, relative_address),
+        );
+
+        execution_return_values.clock_periods += 1;
+
+        execution_return_values
+    }
+
+    // Formats an operand the way ca65 assembles it back to the same bytes. Absolute
+    // addresses below $0100 get an `a:` prefix so they aren't assembled as zero page.
+    fn ca65_operand(&self, address: u16, instruction: Instruction) -> String {
+        let zero_page = || self.memory.get_8_bit_value(address as usize + 1);
+        let absolute = || self.memory.get_16_bit_value(address as usize + 1);
+
+        let absolute_operand = || match absolute() < 0x0100 {
+            true => format!("a:${:04x}", absolute()),
+            false => format!("${:04x}", absolute()),
+        };
+
+        match instruction.addressing_mode {
+            AddressingMode::Accumulator => "a".to_string(),
+            AddressingMode::Implied => String::new(),
+            AddressingMode::Relative => format!(
+                "${:04x}",
+                Cpu::calculate_address_from_relative_offset(address.wrapping_add(2), zero_page())
+            ),
+            AddressingMode::ZeroPage => format!("${:02x}", zero_page()),
+            AddressingMode::Immediate => format!("#${:02x}", zero_page()),
+            AddressingMode::ZeroPageX => format!("${:02x},x", zero_page()),
+            AddressingMode::ZeroPageY => format!("${:02x},y", zero_page()),
+            AddressingMode::Absolute => absolute_operand(),
+            AddressingMode::AbsoluteX => format!("{},x", absolute_operand()),
+            AddressingMode::AbsoluteY => format!("{},y", absolute_operand()),
+            AddressingMode::Indirect => format!("(${:04x})", absolute()),
+            AddressingMode::IndirectX => format!("(${:02x},x)", zero_page()),
+            AddressingMode::IndirectY => format!("(${:02x}),y", zero_page()),
+        }
+    }
+
+    fn call_kernal_stub(&mut self) {
+        let Some(stubs) = self.kernal_stubs.as_mut() else {
+            return;
+        };
+
+        match self.registers.pc as usize {
+            CHRIN_ADDRESS => {
+                if let Some(chrin) = stubs.chrin.as_mut() {
+                    self.registers.a = chrin();
+                    self.registers.p.carry_flag = false;
+                }
+            }
+            CHROUT_ADDRESS => {
+                if let Some(chrout) = stubs.chrout.as_mut() {
+                    chrout(self.registers.a);
+                }
+            }
+            STOP_ADDRESS => {
+                if let Some(stop) = stubs.stop.as_mut() {
+                    self.registers.p.zero_flag = stop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn call_trap(&mut self) -> Option<ExecutionReturnValues> {
+        let address = self.registers.pc;
+
+        // The handler is taken out while it runs because it borrows the cpu.
+        let mut handler = self.traps.remove(&address)?;
+
+        handler(self);
+
+        self.traps.entry(address).or_insert(handler);
+
+        let rts = Instruction::binary_search(RTS_OPCODE)?;
+
+        Some((rts.execute)(self, rts))
+    }
+
+    fn calculate_address_from_relative_offset(base_address: u16, offset: u8) -> u16 {
+        match offset & 0x80 {
+            0x80 => {
+                let positive_offset = !offset + 1;
+                base_address.wrapping_sub(positive_offset as u16)
+            }
+            _ => base_address.wrapping_add(offset as u16),
+        }
+    }
+
+    // Returns `true` if a watched flag changed since the last instruction.
+    fn check_flag_watches(&mut self) -> bool {
+        let mut changed = false;
+
+        for (flag, previous) in self.flag_watches.iter_mut() {
+            let current = self.registers.p.is_set(*flag);
+
+            changed |= current != *previous;
+            *previous = current;
+        }
+
+        changed
+    }
+
+    fn check_breakpoints(&mut self) -> bool {
+        let pc = self.registers.pc;
+        let mut hit = false;
+
+        if self.breakpoints.contains(&pc) {
+            *self.breakpoint_hits.entry(pc).or_insert(0) += 1;
+            hit = true;
+        }
+
+        for i in 0..self.conditional_breakpoints.len() {
+            let breakpoint = &self.conditional_breakpoints[i];
+
+            if breakpoint.address.is_none_or(|address| address == pc)
+                && (breakpoint.condition)(self)
+            {
+                self.conditional_breakpoints[i].hit_count += 1;
+                hit = true;
+            }
+        }
+
+        if !self.watchpoints.is_empty() {
+            if let Some(instruction) = self.get_instruction_for_opcode(pc as usize) {
+                let reads = instruction.reads_memory();
+                let writes = instruction.writes_memory();
+
+                if let Some(address) = self.get_effective_address(instruction) {
+
+                    for watchpoint in self.watchpoints.iter_mut() {
+                        let accessed = match watchpoint.kind {
+                            WatchKind::Read => reads,
+                            WatchKind::Write => writes,
+                        };
+
+                        if accessed && watchpoint.address as usize == address {
+                            watchpoint.hit_count += 1;
+                            hit = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        hit
+    }
+
+    fn compare(&mut self, register_value: u8, value: u8) {
+        let result = (register_value as u16).wrapping_sub(value as u16);
+
+        self.set_zero_flag(result as u8);
+        self.set_negative_flag(result as u8);
+        self.set_carry_flag(!result);
+    }
+
+    fn crosses_boundary_by_address_offset(address: u16, offset: u8) -> bool {
+        address & 0xff00 != address.wrapping_add(offset as u16) & 0xff00
+    }
+
+    fn crosses_boundary_by_two_addresses(base_address: u16, address: u16) -> bool {
+        base_address & 0xff00 != address & 0xff00
+    }
+
+    fn debugger_help() -> String {
+        let mut output = "\r\n".to_string();
+
+        for (command, description) in DEBUGGER_COMMANDS {
+            output.push_str(&format!("{:<7} - {}\r\n", command, description));
+        }
+
+        output
+    }
+
+    fn debug_display_memory(&self, address: u16, length: u16) -> String {
+        let mut lines = Vec::new();
+
+        for row in (0..length).step_by(16) {
+            let row_address = address.wrapping_add(row);
+
+            let mut line = format!("{:04X}:", row_address);
+
+            for i in 0..16.min(length - row) {
+                line = format!(
+                    "{} {:02X}",
+                    line,
+                    self.memory.contents[row_address.wrapping_add(i) as usize]
+                );
+            }
+
+            lines.push(line);
+        }
+
+        lines.join("\r\n")
+    }
+
+    fn debug_display_stack(&self) -> String {
+        if self.registers.sp == 0xFF {
+            return "The stack is empty.".to_string();
+        }
+
+        let top = 0x0100 + self.registers.sp as u16 + 1;
+
+        format!(
+            "Top of stack @ {:04X}\r\n{}",
+            top,
+            self.debug_display_memory(top, 0x0200 - top)
+        )
+    }
+
+    fn debug_history(&self) -> String {
+        if self.debug_history.is_empty() {
+            return "No commands entered.".to_string();
+        }
+
+        self.debug_history
+            .iter()
+            .enumerate()
+            .map(|(i, command)| format!("{:>2}  {}\r\n", i + 1, command))
+            .collect()
+    }
+
+    fn debug_toggle_breakpoint(&mut self, breakpoint: u16) -> String {
+        if self.toggle_breakpoint(breakpoint) {
+            return format!("Breakpoint added @ {:04X}", breakpoint);
+        }
+
+        format!("Breakpoint removed @ {:04X}", breakpoint)
+    }
+
+    // Addresses wrap at $FFFF. The accumulator, implied and relative modes resolve
+    // to the byte after the opcode, which the 6502 reads for them too. It's the
+    // operand for the relative mode and a discarded read for the others.
+    fn get_address(&self, instruction: Instruction) -> (usize, bool) {
+        match instruction.addressing_mode {
+            AddressingMode::Accumulator
+            | AddressingMode::Implied
+            | AddressingMode::Relative
+            | AddressingMode::Immediate => (self.registers.pc.wrapping_add(1) as usize, false),
+            AddressingMode::ZeroPage => {
+                let zero_page_offset = self
+                    .memory
+                    .get_8_bit_value(self.registers.pc.wrapping_add(1) as usize);
+
+                (zero_page_offset as usize, false)
+            }
+            AddressingMode::ZeroPageX => {
+                let zero_page_offset = self
+                    .memory
+                    .get_8_bit_value(self.registers.pc.wrapping_add(1) as usize);
+
+                (zero_page_offset.wrapping_add(self.registers.x) as usize, false)
+            }
+            AddressingMode::ZeroPageY => {
+                let zero_page_offset = self
+                    .memory
+                    .get_8_bit_value(self.registers.pc.wrapping_add(1) as usize);
+
+                (zero_page_offset.wrapping_add(self.registers.y) as usize, false)
+            }
+            AddressingMode::Absolute => {
+                let address = self
+                    .memory
+                    .get_16_bit_value(self.registers.pc.wrapping_add(1) as usize);
+
+                (address as usize, false)
+            }
+            AddressingMode::AbsoluteX => {
+                let address = self
+                    .memory
+                    .get_16_bit_value(self.registers.pc.wrapping_add(1) as usize);
+
+                (
+                    address.wrapping_add(self.registers.x as u16) as usize,
+                    Cpu::crosses_boundary_by_address_offset(address, self.registers.x),
+                )
+            }
+            AddressingMode::AbsoluteY => {
+                let address = self
+                    .memory
+                    .get_16_bit_value(self.registers.pc.wrapping_add(1) as usize);
+
+                (
+                    address.wrapping_add(self.registers.y as u16) as usize,
+                    Cpu::crosses_boundary_by_address_offset(address, self.registers.y),
+                )
+            }
+            AddressingMode::Indirect => {
+                let indirect_address = self
+                    .memory
+                    .get_16_bit_value(self.registers.pc.wrapping_add(1) as usize);
+
+                // The NMOS 6502 doesn't carry into the pointer's high byte, so JMP ($xxFF)
+                // reads the target's high byte from $xx00. The 65C02 fixed this.
+                let msb_address = if self.variant == CpuVariant::Wdc65C02 {
+                    indirect_address.wrapping_add(1)
+                } else {
+                    indirect_address & 0xFF00 | (indirect_address as u8).wrapping_add(1) as u16
+                };
+
+                let lsb = self.memory.get_8_bit_value(indirect_address as usize);
+                let msb = self.memory.get_8_bit_value(msb_address as usize);
+
+                ((msb as usize) << 8 | lsb as usize, false)
+            }
+            AddressingMode::IndirectX => {
+                let indirect_address = self
+                    .memory
+                    .get_8_bit_value(self.registers.pc.wrapping_add(1) as usize)
+                    .wrapping_add(self.registers.x);
+                let address = self.memory.zp_read_u16(indirect_address);
+
+                (address as usize, false)
+            }
+            AddressingMode::IndirectY => {
+                let indirect_address = self
+                    .memory
+                    .get_8_bit_value(self.registers.pc.wrapping_add(1) as usize);
+                let address = self.memory.zp_read_u16(indirect_address);
+
+                (
+                    address.wrapping_add(self.registers.y as u16) as usize,
+                    Cpu::crosses_boundary_by_address_offset(address, self.registers.y),
+                )
+            }
+        }
+    }
+
+    fn execute_instruction(&mut self) -> Option<ExecutionReturnValues> {
+        if self.halted {
+            return None;
+        }
+
+        if !self.rdy {
+            self.cycles += 1;
+
+            self.tick_peripherals(1);
+
+            return Some(ExecutionReturnValues {
+                bytes: 0,
+                clock_periods: 1,
+                set_program_counter: true,
+                accessed_io: false,
+            });
+        }
+
+        let decoded = match self.pre_exec_hook.is_some() || self.post_exec_hook.is_some() {
+            true => self.decode_instruction(self.registers.pc as usize),
+            false => None,
+        };
+
+        if let (Some(mut hook), Some(decoded)) = (self.pre_exec_hook.take(), decoded.as_ref()) {
+            hook(self, decoded);
+            self.pre_exec_hook.get_or_insert(hook);
+        }
+
+        let execution_return_values = self.execute_opcode()?;
+
+        if !execution_return_values.set_program_counter {
+            self.registers.pc = self
+                .registers
+                .pc
+                .wrapping_add(execution_return_values.bytes as u16);
+        }
+
+        self.cycles += execution_return_values.clock_periods as u64;
+        self.instructions += 1;
+
+        self.tick_peripherals(execution_return_values.clock_periods);
+
+        if let (Some(mut hook), Some(decoded)) = (self.post_exec_hook.take(), decoded.as_ref()) {
+            hook(self, decoded);
+            self.post_exec_hook.get_or_insert(hook);
+        }
+
+        if self.check_flag_watches() {
+            self.debug_stepping = true;
+        }
+
+        Some(execution_return_values)
+    }
+
+    fn execute_undefined_opcode(&mut self) -> Option<ExecutionReturnValues> {
+        let opcode = self.memory.get_8_bit_value(self.registers.pc as usize);
+
+        if !self.undefined_opcodes_as_nops {
+            self.halted = self.variant != CpuVariant::Wdc65C02 && is_jam_opcode(opcode);
+
+            return None;
+        }
+
+        Some(ExecutionReturnValues {
+            bytes: Cpu::undefined_opcode_length(opcode),
+            clock_periods: 2,
+            set_program_counter: false,
+            accessed_io: false,
+        })
+    }
+
+    fn get_effective_address(&self, instruction: Instruction) -> Option<usize> {
+        if !instruction.reads_memory() && !instruction.writes_memory() {
+            return None;
+        }
+
+        let (address, _) = self.get_address(instruction);
+
+        Some(address)
+    }
+
+    fn get_instruction_for_opcode(&self, location: usize) -> Option<Instruction> {
+        let opcode = self.memory.get_8_bit_value(location);
+
+        Instruction::lookup(opcode, self.variant)
+    }
+
+    fn get_value(&self, instruction: Instruction) -> (u8, bool) {
+        let (address, crossed_boundary) = self.get_address(instruction);
+
+        (self.memory.get_8_bit_value(address), crossed_boundary)
+    }
+
+    fn handle_interrupt(&mut self, kind: InterruptKind) {
+        let instruction_start_time = self.clock.now();
+
+        self.cycles += 7;
+
+        self.tick_peripherals(7);
+
+        // Cycles 1 and 2 fetch the opcode at the program counter twice and discard it.
+        self.memory.get_8_bit_value(self.registers.pc as usize);
+        self.memory.get_8_bit_value(self.registers.pc as usize);
+
+        // Cycles 3 to 5 push the high and low bytes of the program counter and then the status.
+        self.push_u8((self.registers.pc >> 8) as u8);
+        self.push_u8(self.registers.pc as u8);
+        self.push_u8(self.registers.p.to_pushed_byte(false));
+
+        self.registers.p.interrupt_disable_flag = true;
+
+        if self.variant == CpuVariant::Wdc65C02 {
+            self.registers.p.decimal_flag = false;
+        }
+
+        let vector = match kind {
+            InterruptKind::Nmi => {
+                self.nmi_triggered = false;
+                NMI_VECTOR
+            }
+            InterruptKind::Irq => {
+                self.irq_triggered = false;
+                IRQ_BRK_VECTOR
+            }
+        };
+
+        // Cycles 6 and 7 fetch the low and then the high byte of the vector.
+        let lsb = self.memory.get_8_bit_value(vector);
+        let msb = self.memory.get_8_bit_value(vector + 1);
+
+        self.registers.pc = (msb as u16) << 8 | lsb as u16;
+
+        self.call_depth += 1;
+
+        self.throttle(instruction_start_time, 7);
+    }
+
+    fn exceeds_max_call_depth(&self) -> bool {
+        self.max_call_depth
+            .is_some_and(|max_call_depth| self.call_depth > max_call_depth)
+    }
+
+    fn is_decimal_mode(&self) -> bool {
+        self.registers.p.decimal_flag && self.variant.has_decimal_mode()
+    }
+
+    fn is_unhandled_brk(&self) -> bool {
+        self.memory.get_8_bit_value(self.registers.pc as usize) == 0x00
+            && self.memory.get_16_bit_value(IRQ_BRK_VECTOR) == 0x0000
+    }
+
+    // Returns the address a branch, JMP absolute or JSR at `address` transfers control to.
+    fn jump_target(&self, address: u16, instruction: Instruction) -> Option<u16> {
+        match (instruction.opcode, instruction.addressing_mode) {
+            (_, AddressingMode::Relative) => Some(Cpu::calculate_address_from_relative_offset(
+                address.wrapping_add(2),
+                self.memory.get_8_bit_value(address as usize + 1),
+            )),
+            // JSR and JMP absolute.
+            (0x20 | 0x4C, _) => {
+                Some(self.memory.get_16_bit_value(address as usize + 1))
+            }
+            _ => None,
+        }
+    }
+
+    // The NMOS 6502 polls its interrupt lines during the last cycle of an
+    // instruction. CLI and PLP change the interrupt disable flag after that
+    // poll, so an IRQ that is pending when they clear the flag isn't serviced
+    // until one more instruction has executed. RTI pulls the flags before the
+    // poll, so a pending IRQ is serviced right after it.
+    fn poll_interrupts(&mut self) {
+        self.apply_replayed_inputs();
+        self.record_interrupt_lines();
+
+        // A jammed cpu ignores NMI and IRQ until it's reset.
+        if self.halted || !self.rdy {
+            return;
+        }
+
+        let skip_irq = self.skip_next_irq;
+
+        self.skip_next_irq = false;
+
+        if let Some(kind) = self.pending_interrupt(skip_irq) {
+            self.handle_interrupt(kind);
+
+            // Servicing the interrupt clears its line, which isn't an external input.
+            if let Some(recording) = &mut self.recording {
+                recording.lines = (self.nmi_triggered, self.irq_triggered);
+            }
+        }
+    }
+
+    // An NMI takes priority over an IRQ. When both are pending the NMI is
+    // serviced and the IRQ stays pending until the next instruction boundary
+    // where the interrupt disable flag is clear.
+    fn pending_interrupt(&self, skip_irq: bool) -> Option<InterruptKind> {
+        if self.nmi_triggered {
+            Some(InterruptKind::Nmi)
+        } else if self.irq_triggered && !self.registers.p.interrupt_disable_flag && !skip_irq {
+            Some(InterruptKind::Irq)
+        } else {
+            None
+        }
+    }
+
+    fn record_input(&mut self, input: Input) {
+        if let Some(recording) = &mut self.recording {
+            recording.log.events.push(InputEvent {
+                cycles: self.cycles,
+                input,
+            });
+        }
+    }
+
+    fn record_interrupt_lines(&mut self) {
+        let Some(recording) = &self.recording else {
+            return;
+        };
+
+        let (nmi, irq) = recording.lines;
+
+        if self.nmi_triggered != nmi {
+            self.record_input(Input::Nmi(self.nmi_triggered));
+        }
+
+        if self.irq_triggered != irq {
+            self.record_input(Input::Irq(self.irq_triggered));
+        }
+
+        if let Some(recording) = &mut self.recording {
+            recording.lines = (self.nmi_triggered, self.irq_triggered);
+        }
+    }
+
+    fn apply_replayed_inputs(&mut self) {
+        let Some(replaying) = &mut self.replaying else {
+            return;
+        };
+
+        while let Some(event) = replaying.log.events.get(replaying.next) {
+            if event.cycles > self.cycles {
+                return;
+            }
+
+            match event.input {
+                Input::Nmi(triggered) => self.nmi_triggered = triggered,
+                Input::Irq(triggered) => self.irq_triggered = triggered,
+                Input::Rdy(ready) => self.rdy = ready,
+            }
+
+            replaying.next += 1;
+        }
+
+        self.replaying = None;
+    }
+
+    fn trace(&mut self) {
+        let Some(mut callback) = self.trace_callback.take() else {
+            return;
+        };
+
+        if self
+            .trace_filter
+            .as_ref()
+            .is_none_or(|range| range.contains(&self.registers.pc))
+        {
+            let record = TraceRecord {
+                pc: self.registers.pc,
+                a: self.registers.a,
+                x: self.registers.x,
+                y: self.registers.y,
+                p: self.registers.p.to_byte(),
+                sp: self.registers.sp,
+                cycles: self.cycles,
+                instruction: self.decode_instruction(self.registers.pc as usize),
+            };
+
+            callback(&record);
+        }
+
+        self.trace_callback = Some(callback);
+    }
+
+    fn unexecuted_opcode_exit(&self) -> RunExit {
+        match self.halted {
+            true => RunExit::Jammed(self.registers.pc),
+            false => RunExit::Error(self.unrecognized_opcode_error()),
+        }
+    }
+
+    fn unrecognized_opcode_error(&self) -> CpuError {
+        CpuError::UnrecognizedOpcode {
+            opcode: self.current_opcode(),
+            address: self.registers.pc,
+        }
+    }
+
+    // Guesses the length of an undefined opcode from the column it sits in. Columns
+    // $x0 to $x7 are zero page, immediate or indirect modes, $x8 and $xA are
+    // implied, $x9 and $xB are immediate in even rows and absolute,Y in odd rows
+    // and $xC to $xF are absolute modes.
+    fn undefined_opcode_length(opcode: u8) -> u8 {
+        match opcode & 0x0F {
+            0x00..=0x07 => 2,
+            0x08 | 0x0A => 1,
+            0x09 | 0x0B if opcode & 0x10 == 0 => 2,
+            _ => 3,
+        }
+    }
+
+    fn subtract_with_borrow(&mut self, value: u8) {
+        // In binary mode A - M - borrow is the same as A + !M + carry.
+        match self.is_decimal_mode() {
+            true => self.subtract_with_borrow_decimal(value),
+            false => self.add_with_carry(!value),
+        }
+    }
+
+    // The NMOS 6502 sets N, V, Z and C exactly as it does in binary mode. Only A gets the
+    // corrected BCD result. The 65C02 sets N and Z from the corrected result.
+    // See http://www.6502.org/tutorials/decimal_mode.html.
+    fn subtract_with_borrow_decimal(&mut self, value: u8) {
+        let (carry, borrow) = match self.registers.p.carry_flag {
+            true => (1u16, 0i16),
+            false => (0u16, 1i16),
+        };
+
+        let binary_result = self.registers.a as u16 + !value as u16 + carry;
+
+        self.set_zero_flag(binary_result as u8);
+        self.set_negative_flag(binary_result as u8);
+        self.set_overflow_flag(self.registers.a, !value, binary_result as u8);
+        self.set_carry_flag(binary_result);
+
+        let a = self.registers.a as i16;
+
+        let mut low_nibble = (a & 0x0F) - (value as i16 & 0x0F) - borrow;
+
+        if low_nibble < 0 {
+            low_nibble = ((low_nibble - 0x06) & 0x0F) - 0x10;
+        }
+
+        let mut result = (a & 0xF0) - (value as i16 & 0xF0) + low_nibble;
+
+        if result < 0 {
+            result -= 0x60;
+        }
+
+        self.registers.a = result as u8;
+
+        if self.variant == CpuVariant::Wdc65C02 {
+            self.set_negative_flag(self.registers.a);
+            self.set_zero_flag(self.registers.a);
+        }
+    }
+
+    fn trap_state(&self) -> (u16, u8, u8, u8, u8, u8) {
+        (
+            self.registers.pc,
+            self.registers.a,
+            self.registers.x,
+            self.registers.y,
+            self.registers.p.to_byte(),
+            self.registers.sp,
+        )
+    }
+
+    /// Sleeps for whatever is left of the time `cycles` take at the cpu's clock speed.
+    fn throttle(&mut self, start_time: Duration, cycles: u64) {
+        let elapsed_time = self.clock.now().saturating_sub(start_time);
+        let target_time = Duration::from_secs_f64(self.cycle_duration * cycles as f64);
+
+        if target_time > elapsed_time {
+            self.clock.sleep(target_time - elapsed_time);
+        }
+    }
+
+    fn tick_peripherals(&mut self, cycles: u8) {
+        for peripheral in self.peripherals.iter_mut() {
+            match peripheral.tick(cycles, &mut self.memory) {
+                Some(InterruptKind::Nmi) => self.nmi_triggered = true,
+                Some(InterruptKind::Irq) => self.irq_triggered = true,
+                None => {}
+            }
+        }
+    }
+
+    fn pull_u8(&mut self) -> u8 {
+        self.registers.sp = self.registers.sp.wrapping_add(1);
+
+        let address = 0x0100 + self.registers.sp as usize;
+
+        self.memory.get_8_bit_value(address)
+    }
+
+    // Pulled a byte at a time so the stack wraps within page one.
+    fn pull_u16(&mut self) -> u16 {
+        let lsb = self.pull_u8();
+        let msb = self.pull_u8();
+
+        (msb as u16) << 8 | lsb as u16
+    }
+
+    fn push_u8(&mut self, value: u8) {
+        let stack_pointer: usize = STACK_BASE_ADDRESS + self.registers.sp as usize;
+
+        self.memory.set_8_bit_value(stack_pointer, value);
+
+        self.registers.sp = self.registers.sp.wrapping_sub(1);
+    }
+
+    // Pushed a byte at a time so the stack wraps within page one.
+    fn push_u16(&mut self, value: u16) {
+        self.push_u8((value >> 8) as u8);
+        self.push_u8(value as u8);
+    }
+
+    fn save_register(&mut self, instruction: Instruction, value: u8) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        self.memory.set_8_bit_value(address, value);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn record_debug_history(&mut self, input: &str) {
+        let input = input.trim();
+
+        if input.is_empty() {
+            return;
+        }
+
+        if self.debug_history.len() == DEBUGGER_HISTORY_LENGTH {
+            self.debug_history.remove(0);
+        }
+
+        self.debug_history.push(input.to_string());
+    }
+
+    fn set_carry_flag(&mut self, result: u16) {
+        self.registers.p.carry_flag = result > 0xff;
+    }
+
+    fn set_negative_flag(&mut self, value: u8) {
+        self.registers.p.negative_flag = value & 0x80 != 0;
+    }
+
+    fn set_overflow_flag(&mut self, a: u8, b: u8, result: u8) {
+        // Overflow occurs if both numbers have the same sign and
+        // the result has a different sign.
+
+        // !(a ^ b) - 0x80 bit will be set if both signs are true.
+        // (a ^ result) - 0x80 bit will be set if result has a different sign.
+
+        // Based on a StackOverflow answer: https://stackoverflow.com/questions/29193303/6502-emulation-proper-way-to-implement-adc-and-sbc
+
+        self.registers.p.overflow_flag = (!(a ^ b) & (a ^ result) & 0x80) != 0;
+    }
+
+    fn set_zero_flag(&mut self, value: u8) {
+        self.registers.p.zero_flag = value == 0;
+    }
+
+    /***************************************************
+     *
+     * Implementations of the 6502 instructions.
+     *
+     ***************************************************/
+
+    fn adc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.add_with_carry(value);
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    // ALR ANDs the operand into the accumulator and then shifts it right.
+    fn alr_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, _) = self.get_value(instruction);
+
+        let value = self.registers.a & value;
+
+        self.registers.p.carry_flag = value & 0x01 == 0x01;
+
+        let result = value >> 1;
+
+        self.registers.p.negative_flag = false;
+        self.set_zero_flag(result);
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    // ANC ANDs the operand into the accumulator and copies the negative flag into the carry.
+    fn anc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, _) = self.get_value(instruction);
+
+        let result = self.registers.a & value;
+
+        self.set_zero_flag(result);
+        self.set_negative_flag(result);
+        self.registers.p.carry_flag = self.registers.p.negative_flag;
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn and_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        let result = self.registers.a & value;
+
+        self.set_zero_flag(result);
+        self.set_negative_flag(result);
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    // ARR ANDs the operand into the accumulator and then rotates it right, but the carry
+    // and overflow flags come from bits 6 and 5 of the result rather than the rotate.
+    // In decimal mode the result is also BCD adjusted, and the negative flag is the old carry.
+    fn arr_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, _) = self.get_value(instruction);
+
+        let value = self.registers.a & value;
+
+        let mut result = value >> 1;
+
+        if self.registers.p.carry_flag {
+            result |= 0x80;
+        }
+
+        if self.is_decimal_mode() {
+            self.registers.p.negative_flag = self.registers.p.carry_flag;
+            self.set_zero_flag(result);
+            self.registers.p.overflow_flag = (value ^ result) & 0x40 == 0x40;
+
+            if (value & 0x0F) + (value & 0x01) > 0x05 {
+                result = (result & 0xF0) | (result.wrapping_add(0x06) & 0x0F);
+            }
+
+            self.registers.p.carry_flag = (value & 0xF0) as u16 + (value & 0x10) as u16 > 0x50;
+
+            if self.registers.p.carry_flag {
+                result = (result & 0x0F) | (result.wrapping_add(0x60) & 0xF0);
+            }
+        } else {
+            self.set_negative_flag(result);
+            self.set_zero_flag(result);
+            self.registers.p.carry_flag = result & 0x40 == 0x40;
+            self.registers.p.overflow_flag = ((result >> 6) ^ (result >> 5)) & 0x01 == 0x01;
+        }
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn asl_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, value): (Option<usize>, u8) = match instruction.addressing_mode {
+            AddressingMode::Accumulator => (None, self.registers.a),
+            _ => {
+                let (address, _) = self.get_address(instruction);
+                (Some(address), self.memory.get_8_bit_value(address))
+            }
+        };
+
+        self.registers.p.carry_flag = value & 0x80 == 0x80;
+
+        let result = value << 1;
+
+        self.set_zero_flag(result);
+        self.set_negative_flag(result);
+
+        if address.is_none() {
+            self.registers.a = result;
+        } else {
+            self.memory.set_8_bit_value(address.unwrap(), result);
+        }
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn bcc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.branch(instruction, !self.registers.p.carry_flag)
+    }
+
+    fn bcs_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.branch(instruction, self.registers.p.carry_flag)
+    }
+
+    fn beq_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.branch(instruction, self.registers.p.zero_flag)
+    }
+
+    fn bit_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, _) = self.get_value(instruction);
+
+        let result = self.registers.a & value;
+
+        self.set_zero_flag(result);
+
+        // The 65C02's BIT #imm only affects the zero flag.
+        if !matches!(instruction.addressing_mode, AddressingMode::Immediate) {
+            self.registers.p.negative_flag = value & 0x80 != 0;
+            self.registers.p.overflow_flag = value & 0x40 != 0;
+        }
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn bmi_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.branch(instruction, self.registers.p.negative_flag)
+    }
+
+    fn bne_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.branch(instruction, !self.registers.p.zero_flag)
+    }
+
+    fn bpl_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.branch(instruction, !self.registers.p.negative_flag)
+    }
+
+    fn brk_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.push_u16(self.registers.pc.wrapping_add(2));
+
+        self.push_u8(self.registers.p.to_pushed_byte(true));
+        self.registers.p.break_flag = false;
+
+        self.registers.p.interrupt_disable_flag = true;
+
+        // The 65C02 clears the decimal flag, which the NMOS 6502 leaves unchanged.
+        if self.variant == CpuVariant::Wdc65C02 {
+            self.registers.p.decimal_flag = false;
+        }
+
+        self.registers.pc = self.memory.get_16_bit_value(IRQ_BRK_VECTOR);
+
+        self.call_depth += 1;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn bvc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.branch(instruction, !self.registers.p.overflow_flag)
+    }
+
+    fn bvs_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.branch(instruction, self.registers.p.overflow_flag)
+    }
+
+    fn clc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.p.carry_flag = false;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn cld_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.p.decimal_flag = false;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn cli_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.skip_next_irq = self.registers.p.interrupt_disable_flag;
+        self.registers.p.interrupt_disable_flag = false;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn clv_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.p.overflow_flag = false;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn cmp_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.compare(self.registers.a, value);
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn cpx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.compare(self.registers.x, value);
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn cpy_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.compare(self.registers.y, value);
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    // DCP decrements memory and then compares the accumulator with the result. Like the
+    // other read-modify-write instructions, indexing never adds a cycle.
+    fn dcp_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        let result = self.memory.get_8_bit_value(address).wrapping_sub(1);
+
+        self.memory.set_8_bit_value(address, result);
+
+        self.compare(self.registers.a, result);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn dec_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        let result = self.memory.get_8_bit_value(address).wrapping_sub(1);
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.memory.set_8_bit_value(address, result);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn dex_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let result = self.registers.x.wrapping_sub(1);
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.registers.x = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn dey_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let result = self.registers.y.wrapping_sub(1);
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.registers.y = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn eor_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        let result = self.registers.a ^ value;
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn inc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        let result = self.memory.get_8_bit_value(address).wrapping_add(1);
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.memory.set_8_bit_value(address, result);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn inx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let result = self.registers.x.wrapping_add(1);
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.registers.x = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn iny_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let result = self.registers.y.wrapping_add(1);
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.registers.y = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    // ISC increments memory and then subtracts the result from the accumulator. Like the
+    // other read-modify-write instructions, indexing never adds a cycle.
+    fn isc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        let result = self.memory.get_8_bit_value(address).wrapping_add(1);
+
+        self.memory.set_8_bit_value(address, result);
+
+        self.subtract_with_borrow(result);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn jmp_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        self.registers.pc = address as u16;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn jsr_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        self.push_u16(self.registers.pc.wrapping_add(2));
+
+        self.registers.pc = address as u16;
+
+        self.call_depth += 1;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn lda_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.set_zero_flag(value);
+        self.set_negative_flag(value);
+
+        self.registers.a = value;
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn lax_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.set_zero_flag(value);
+        self.set_negative_flag(value);
+
+        self.registers.a = value;
+        self.registers.x = value;
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn ldx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.set_negative_flag(value);
+        self.set_zero_flag(value);
+
+        self.registers.x = value;
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn ldy_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.set_negative_flag(value);
+        self.set_zero_flag(value);
+
+        self.registers.y = value;
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn lsr_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, value): (Option<usize>, u8) = match instruction.addressing_mode {
+            AddressingMode::Accumulator => (None, self.registers.a),
+            _ => {
+                let (address, _) = self.get_address(instruction);
+                (Some(address), self.memory.get_8_bit_value(address))
+            }
+        };
+
+        self.registers.p.carry_flag = value & 0x01 == 0x01;
+
+        let result = (value >> 1) & 0x7F;
+
+        self.registers.p.negative_flag = false;
+        self.set_zero_flag(result);
+
+        if address.is_none() {
+            self.registers.a = result;
+        } else {
+            self.memory.set_8_bit_value(address.unwrap(), result);
+        }
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn nop_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        // The undocumented absolute,X NOPs take an extra cycle when they cross a page.
+        let (_, crossed_boundary) = self.get_address(instruction);
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn ora_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        let result = self.registers.a | value;
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    fn pha_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.push_u8(self.registers.a);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn php_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.push_u8(self.registers.p.to_pushed_byte(true));
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn pla_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.a = self.pull_u8();
+
+        self.set_negative_flag(self.registers.a);
+        self.set_zero_flag(self.registers.a);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn plp_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let mut flags = self.pull_u8();
+
+        flags &= !StatusFlags::BREAK_FLAG;
+
+        self.skip_next_irq = self.registers.p.interrupt_disable_flag
+            && flags & StatusFlags::INTERRUPT_FLAG == 0;
+
+        self.registers.p.from_byte(flags);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    // RLA rotates memory left through the carry and then ANDs the result into the accumulator.
+    fn rla_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        let value = self.memory.get_8_bit_value(address);
+
+        let mut rotated = value << 1;
+
+        if self.registers.p.carry_flag {
+            rotated |= 0x01;
+        }
+
+        self.registers.p.carry_flag = value & 0x80 == 0x80;
+
+        self.memory.set_8_bit_value(address, rotated);
+
+        let result = self.registers.a & rotated;
+
+        self.set_zero_flag(result);
+        self.set_negative_flag(result);
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn rol_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, value): (Option<usize>, u8) = match instruction.addressing_mode {
+            AddressingMode::Accumulator => (None, self.registers.a),
+            _ => {
+                let (address, _) = self.get_address(instruction);
+                (Some(address), self.memory.get_8_bit_value(address))
+            }
+        };
+
+        let high_bit = value & 0x80;
+
+        let mut result = value << 1;
+
+        if self.registers.p.carry_flag {
+            result |= 0x01;
+        }
+
+        self.registers.p.carry_flag = high_bit == 0x80;
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        if address.is_none() {
+            self.registers.a = result;
+        } else {
+            self.memory.set_8_bit_value(address.unwrap(), result);
+        }
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn ror_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, value): (Option<usize>, u8) = match instruction.addressing_mode {
+            AddressingMode::Accumulator => (None, self.registers.a),
+            _ => {
+                let (address, _) = self.get_address(instruction);
+                (Some(address), self.memory.get_8_bit_value(address))
+            }
+        };
+
+        let low_bit = value & 0x01;
+
+        let mut result = value >> 1;
+
+        if self.registers.p.carry_flag {
+            result |= 0x80;
+        }
+
+        self.registers.p.carry_flag = low_bit == 0x01;
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        if address.is_none() {
+            self.registers.a = result;
+        } else {
+            self.memory.set_8_bit_value(address.unwrap(), result);
+        }
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    // RRA rotates memory right through the carry and then adds the result to the accumulator
+    // with the carry that was rotated out.
+    fn rra_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        let value = self.memory.get_8_bit_value(address);
+
+        let mut rotated = value >> 1;
+
+        if self.registers.p.carry_flag {
+            rotated |= 0x80;
+        }
+
+        self.registers.p.carry_flag = value & 0x01 == 0x01;
+
+        self.memory.set_8_bit_value(address, rotated);
+
+        self.add_with_carry(rotated);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn rti_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let mut flags = self.pull_u8();
+
+        flags &= !StatusFlags::BREAK_FLAG;
+
+        self.registers.p.from_byte(flags);
+
+        let address = self.pull_u16();
+
+        self.registers.pc = address;
+
+        self.call_depth = self.call_depth.saturating_sub(1);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn rts_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let address = self.pull_u16();
+
+        self.registers.pc = address.wrapping_add(1);
+
+        self.call_depth = self.call_depth.saturating_sub(1);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn sax_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.save_register(instruction, self.registers.a & self.registers.x)
+    }
+
+    fn sbc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, crossed_boundary) = self.get_value(instruction);
+
+        self.subtract_with_borrow(value);
+
+        ExecutionReturnValues::new(instruction, crossed_boundary)
+    }
+
+    // SBX subtracts the operand from the accumulator ANDed with X, without a borrow, and
+    // stores the result in X. The flags are set like CMP.
+    fn sbx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (value, _) = self.get_value(instruction);
+
+        let a_and_x = self.registers.a & self.registers.x;
+
+        self.compare(a_and_x, value);
+
+        self.registers.x = a_and_x.wrapping_sub(value);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn sec_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.p.carry_flag = true;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn sed_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.p.decimal_flag = true;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn sei_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.p.interrupt_disable_flag = true;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    // SLO shifts memory left and then ORs the result into the accumulator.
+    fn slo_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        let value = self.memory.get_8_bit_value(address);
+
+        self.registers.p.carry_flag = value & 0x80 == 0x80;
+
+        let shifted = value << 1;
+
+        self.memory.set_8_bit_value(address, shifted);
+
+        let result = self.registers.a | shifted;
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    // SRE shifts memory right and then exclusive ORs the result into the accumulator.
+    fn sre_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        let (address, _) = self.get_address(instruction);
+
+        let value = self.memory.get_8_bit_value(address);
+
+        self.registers.p.carry_flag = value & 0x01 == 0x01;
+
+        let shifted = value >> 1;
+
+        self.memory.set_8_bit_value(address, shifted);
+
+        let result = self.registers.a ^ shifted;
+
+        self.set_negative_flag(result);
+        self.set_zero_flag(result);
+
+        self.registers.a = result;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn sta_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.save_register(instruction, self.registers.a)
+    }
+
+    fn stx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.save_register(instruction, self.registers.x)
+    }
+
+    fn sty_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.save_register(instruction, self.registers.y)
+    }
+
+    fn tax_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.x = self.registers.a;
+
+        self.set_negative_flag(self.registers.x);
+        self.set_zero_flag(self.registers.x);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn tay_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.y = self.registers.a;
+
+        self.set_negative_flag(self.registers.y);
+        self.set_zero_flag(self.registers.y);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn tsx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.x = self.registers.sp;
+
+        self.set_negative_flag(self.registers.x);
+        self.set_zero_flag(self.registers.x);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn txa_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.a = self.registers.x;
+
+        self.set_negative_flag(self.registers.a);
+        self.set_zero_flag(self.registers.a);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn txs_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.sp = self.registers.x;
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+
+    fn tya_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
+        self.registers.a = self.registers.y;
+
+        self.set_negative_flag(self.registers.a);
+        self.set_zero_flag(self.registers.a);
+
+        ExecutionReturnValues::new(instruction, false)
+    }
+}
+
+/// Disassembles a standalone buffer of NMOS 6502 code without a `Cpu` or `Memory`.
+///
+/// Returns a line and its length in bytes for every instruction. Unrecognized opcodes
+/// take one byte and an instruction cut off by the end of the buffer is shown as a
+/// `.BYTE` line.
+///
+/// # Arguments
+/// * `bytes` - The code to disassemble.
+/// * `origin` - The address of the first byte.
+///
+/// # Examples
+/// ```
+/// use rust_6502::cpu::disassemble_bytes;
+///
+/// // LDA #$FF
+/// let lines = disassemble_bytes(&[0xA9, 0xFF], 0x0400);
+///
+/// assert_eq!(lines, vec![("0400  A9 FF    LDA  #$FF".to_string(), 2)]);
+/// ```
+pub fn disassemble_bytes(bytes: &[u8], origin: u16) -> Vec<(String, u8)> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+
+    while offset < bytes.len() {
+        let address = origin.wrapping_add(offset as u16);
+
+        let line = match Instruction::binary_search(bytes[offset]) {
+            Some(instruction) if offset + instruction.bytes as usize <= bytes.len() => {
+                let instruction_bytes = bytes[offset..offset + instruction.bytes as usize].to_vec();
+
+                (
+                    DecodedInstruction::new(&instruction, address, instruction_bytes)
+                        .to_string(),
+                    instruction.bytes,
+                )
+            }
+            Some(_) => (
+                format_byte_line(address, &bytes[offset..]),
+                (bytes.len() - offset) as u8,
+            ),
+            None => (format_unrecognized_line(address, bytes[offset]), 1),
+        };
+
+        offset += line.1 as usize;
+
+        result.push(line);
+    }
+
+    result
+}
+
+fn format_byte_line(address: u16, bytes: &[u8]) -> String {
+    let mut hex_bytes = String::new();
+    let mut values = Vec::new();
+
+    for byte in bytes {
+        hex_bytes = format!("{} {:02X}", hex_bytes, byte);
+        values.push(format!("${:02X}", byte));
+    }
+
+    format!("{:04X} {:<9} .BYTE {}", address, hex_bytes, values.join(","))
+}
+
+fn format_unrecognized_line(address: u16, opcode: u8) -> String {
+    format!("{:04X} {:<9} UNRECONIZED OPCODE", address, format!("{:02X}", opcode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clock::MockClock;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    #[test]
+    fn test_set_zero_flag_when_not_zero() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.p.zero_flag = true;
+
+        cpu.set_zero_flag(0xff);
+
+        assert!(!cpu.registers.p.zero_flag);
+    }
+
+    #[test]
+    fn test_set_zero_flag_when_zero() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.p.zero_flag = false;
+
+        cpu.set_zero_flag(0x00);
+
+        assert!(cpu.registers.p.zero_flag);
+    }
+
+    #[test]
+    fn test_set_overflow_flag_when_two_positives_results_in_a_negative() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.p.overflow_flag = false;
+
+        cpu.set_overflow_flag(0x7f, 0x01, (0x7f + 0x01) as u16 as u8);
+
+        assert!(cpu.registers.p.overflow_flag);
+    }
+
+    #[test]
+    fn test_set_overflow_flag_when_two_positives_results_in_a_positive() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.p.overflow_flag = false;
+
+        cpu.set_overflow_flag(0x7e, 0x01, (0x7e + 0x01) as u16 as u8);
+
+        assert!(!cpu.registers.p.overflow_flag);
+    }
+
+    #[test]
+    fn test_set_overflow_flag_when_two_negatives_results_in_a_positive() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.p.overflow_flag = false;
+
+        cpu.set_overflow_flag(0x80, 0xff, (0x80 + 0xff) as u16 as u8);
+
+        assert!(cpu.registers.p.overflow_flag);
+    }
+
+    #[test]
+    fn test_set_overflow_flag_when_two_negatives_results_in_a_negative() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.p.overflow_flag = false;
+
+        cpu.set_overflow_flag(0x81, 0xff, (0x81 + 0xff) as u16 as u8);
+
+        assert!(!cpu.registers.p.overflow_flag);
+    }
+
+    #[test]
+    fn test_set_carry_flag_when_no_carry() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.p.carry_flag = true;
+
+        cpu.set_carry_flag(0x00ff);
+
+        assert!(!cpu.registers.p.carry_flag);
+    }
+
+    #[test]
+    fn test_set_carry_flag_when_carry() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.p.carry_flag = false;
+
+        cpu.set_carry_flag(0x0100);
+
+        assert!(cpu.registers.p.carry_flag);
+    }
+
+    #[test]
+    fn test_crosses_boundary_not_crossed() {
+        assert!(!Cpu::crosses_boundary_by_address_offset(0x1ffe, 0x01));
+    }
+
+    #[test]
+    fn test_crosses_boundary_crossed() {
+        assert!(Cpu::crosses_boundary_by_address_offset(0x1fff, 0x01));
+    }
+
+    #[test]
+    fn test_pull_u8() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.sp = 0xFE;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x01FF] = 0xFF;
+
+        let result = cpu.pull_u8();
+
+        assert_eq!(cpu.registers.sp, 0xFF);
+        assert_eq!(result, 0xFF);
+    }
+
+    #[test]
+    fn test_pull_u16() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.sp = 0xFD;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x01FE] = 0xFF;
+        cpu.memory.contents[0x01FF] = 0x20;
+
+        let result = cpu.pull_u16();
+
+        assert_eq!(cpu.registers.sp, 0xFF);
+        assert_eq!(result, 0x20FF);
+    }
+
+    #[test]
+    fn test_push_u8() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.sp = 0xff;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x01ff] = 0x00;
+
+        cpu.push_u8(0xff);
+
+        assert_eq!(cpu.registers.sp, 0xfe);
+        assert_eq!(cpu.memory.contents[0x01ff], 0xff);
+    }
+
+    #[test]
+    fn test_push_u16() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.sp = 0xff;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x01fe] = 0x00;
+        cpu.memory.contents[0x01ff] = 0x00;
+        cpu.push_u16(0x0102);
+
+        assert_eq!(cpu.registers.sp, 0xfd);
+        assert_eq!(cpu.memory.contents[0x01fe], 0x02);
+        assert_eq!(cpu.memory.contents[0x01ff], 0x01);
+    }
+
+    #[test]
+    fn test_compare_when_register_is_less_than_value() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x10;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.carry_flag = true;
+
+        cpu.compare(cpu.registers.a, 0x11);
+
+        assert!(cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.carry_flag);
+    }
+
+    #[test]
+    fn test_compare_when_register_is_equal_to_value() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x10;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.carry_flag = false;
+
+        cpu.compare(cpu.registers.a, 0x10);
+
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.carry_flag);
+    }
+
+    #[test]
+    fn test_compare_when_register_is_greater_than_value() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x10;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.carry_flag = false;
+
+        cpu.compare(cpu.registers.a, 0x09);
+
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.carry_flag);
+    }
+
+    #[test]
+    fn test_install_trap() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        // JSR $9000, STA $10
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x20, 0x00, 0x90, 0x85, 0x10]);
+
+        let calls = Rc::new(Cell::new(0));
+        let trap_calls = calls.clone();
+
+        cpu.install_trap(0x9000, move |cpu: &mut Cpu| {
+            trap_calls.set(trap_calls.get() + 1);
+            cpu.registers.a = 0x42;
+        });
+
+        cpu.execute_instruction().unwrap();
+
+        assert_eq!(cpu.registers.pc, 0x9000);
+
+        let return_values = cpu.execute_instruction().unwrap();
+
+        assert_eq!(return_values.clock_periods, 6);
+        assert_eq!(cpu.registers.pc, 0x8003);
+        assert_eq!(cpu.registers.sp, 0xFF);
+
+        cpu.execute_instruction().unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0010], 0x42);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(cpu.cycles(), 15);
+    }
+
+    #[test]
+    fn test_install_kernal_stubs_chrout() {
+        let mut cpu: Cpu = Cpu::new(0x0400, 1_000_000.0);
+        cpu.power_up();
+
+        let output = std::rc::Rc::new(std::cell::RefCell::new(String::new()));
+        let chrout_output = output.clone();
+
+        cpu.install_kernal_stubs(KernalStubs {
+            chrout: Some(Box::new(move |c: u8| chrout_output.borrow_mut().push(c as char))),
+            ..Default::default()
+        });
+
+        // LDA #'H', JSR CHROUT, LDA #'I', JSR CHROUT
+        cpu.memory.save_u8_vector_into_memory(
+            0x0400,
+            vec![0xA9, 0x48, 0x20, 0xD2, 0xFF, 0xA9, 0x49, 0x20, 0xD2, 0xFF],
+        );
+
+        while cpu.registers.pc != 0x040A {
+            let return_values = cpu.execute_opcode().unwrap();
+
+            if !return_values.set_program_counter {
+                cpu.registers.pc += return_values.bytes as u16;
+            }
+        }
+
+        assert_eq!(*output.borrow(), "HI");
+        assert_eq!(cpu.registers.sp, 0xFF);
+    }
+
+    #[test]
+    fn test_run_halts_on_brk_without_handler() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        cpu.halt_on_unhandled_brk = true;
+        cpu.memory.contents[0x8000] = 0xEA;
+        cpu.memory.contents[0x8001] = 0x00;
+
+        let run_exit = cpu.run(Some(|_: &str| "X".to_string()));
+
+        assert_eq!(run_exit, RunExit::BreakWithoutHandler(0x8001));
+        assert_eq!(cpu.registers.pc, 0x8001);
+        assert_eq!(cpu.registers.sp, 0xFF);
+    }
+
+    #[test]
+    fn test_toggle_breakpoint() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+
+        assert!(cpu.toggle_breakpoint(0x8010));
+        assert_eq!(cpu.breakpoints, vec![0x8010]);
+
+        assert!(!cpu.toggle_breakpoint(0x8010));
+        assert!(cpu.breakpoints.is_empty());
+    }
+
+    #[test]
+    fn test_break_when_register() {
+        thread_local! {
+            static PROMPTS: Cell<u32> = const { Cell::new(0) };
+        }
+
+        fn debugger(_output: &str) -> String {
+            PROMPTS.with(|prompts| prompts.set(prompts.get() + 1));
+
+            match PROMPTS.with(|prompts| prompts.get()) {
+                1 => "X".to_string(),
+                _ => "Q".to_string(),
+            }
+        }
+
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        // LDX #$00, INX, BNE $8002
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA2, 0x00, 0xE8, 0xD0, 0xFD]);
+
+        cpu.break_when_register(Register::X, 0x05);
+
+        assert_eq!(cpu.run(Some(debugger)), RunExit::Quit);
+        assert_eq!(PROMPTS.with(|prompts| prompts.get()), 2);
+        assert_eq!(cpu.registers.x, 0x05);
+        assert_eq!(cpu.registers.pc, 0x8003);
+        assert_eq!(cpu.list_breakpoints()[0].hit_count, 1);
+    }
+
+    #[test]
+    fn test_list_breakpoints_and_watchpoints() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        cpu.halt_on_unhandled_brk = true;
+
+        // LDA #$05, STA $0200, NOP, BRK
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA9, 0x05, 0x8D, 0x00, 0x02, 0xEA, 0x00]);
+
+        cpu.toggle_breakpoint(0x8005);
+        cpu.add_conditional_breakpoint(None, |cpu: &Cpu| cpu.registers.a == 0x05);
+        cpu.add_watchpoint(0x0200, WatchKind::Write);
+        cpu.add_watchpoint(0x0201, WatchKind::Read);
+
+        cpu.run(Some(|_: &str| "X".to_string()));
+
+        assert_eq!(
+            cpu.list_breakpoints(),
+            vec![
+                BreakpointInfo {
+                    address: Some(0x8005),
+                    kind: BreakpointKind::Address,
+                    hit_count: 1,
+                },
+                BreakpointInfo {
+                    address: None,
+                    kind: BreakpointKind::Conditional,
+                    hit_count: 3,
+                },
+            ]
+        );
+
+        assert_eq!(
+            cpu.list_watchpoints(),
+            vec![
+                BreakpointInfo {
+                    address: Some(0x0200),
+                    kind: BreakpointKind::Watchpoint(WatchKind::Write),
+                    hit_count: 1,
+                },
+                BreakpointInfo {
+                    address: Some(0x0201),
+                    kind: BreakpointKind::Watchpoint(WatchKind::Read),
+                    hit_count: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execute_opcode_flags_io_access() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x06;
+        cpu.registers.pc = 0x8000;
+
+        let border_color = std::rc::Rc::new(std::cell::Cell::new(0u8));
+        let hook_border_color = border_color.clone();
+
+        cpu.memory
+            .add_write_hook(0xD020, 0xD020, move |_, value| hook_border_color.set(value));
+
+        // STA $D020, STA $0400
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x8D, 0x20, 0xD0, 0x8D, 0x00, 0x04]);
+
+        let return_values = cpu.execute_opcode().unwrap();
+
+        assert!(return_values.accessed_io);
+        assert_eq!(border_color.get(), 0x06);
+
+        cpu.registers.pc += return_values.bytes as u16;
+
+        let return_values = cpu.execute_opcode().unwrap();
+
+        assert!(!return_values.accessed_io);
+        assert_eq!(cpu.memory.contents[0x0400], 0x06);
+    }
+
+    #[test]
+    fn test_current_opcode() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.pc = 0x8003;
+
+        cpu.memory.contents[0x8003] = 0x4C;
+
+        assert_eq!(cpu.current_opcode(), 0x4C);
+    }
+
+    #[test]
+    fn test_disassemble_current() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.pc = 0x8003;
+
+        // JMP $1234
+        cpu.memory
+            .save_u8_vector_into_memory(0x8003, vec![0x4C, 0x34, 0x12]);
+
+        assert_eq!(
+            cpu.disassemble_current(),
+            Some("8003  4C 34 12 JMP  $1234".to_string())
+        );
+
+        cpu.memory.contents[0x8003] = 0x9E;
+
+        assert_eq!(cpu.disassemble_current(), None);
+    }
+
+    #[test]
+    fn test_disassemble_undocumented_opcode() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+
+        // LAX $30, LDA $30
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA7, 0x30, 0xA5, 0x30]);
+
+        assert_eq!(
+            cpu.disassemble_opcode(0x8000),
+            Some(("8000  A7 30   *LAX  $30".to_string(), 2))
+        );
+        assert_eq!(
+            cpu.disassemble_opcode(0x8002),
+            Some(("8002  A5 30    LDA  $30".to_string(), 2))
+        );
+    }
+
+    #[test]
+    fn test_decode_instruction_relative_offset() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+
+        // BNE $8010 from 0x8014, a backward branch.
+        cpu.memory.save_u8_vector_into_memory(0x8014, vec![0xD0, 0xFA]);
+
+        let decoded = cpu.decode_instruction(0x8014).unwrap();
+
+        assert_eq!(decoded.bytes, vec![0xD0, 0xFA]);
+        assert_eq!(decoded.operand, "$8010");
+        assert_eq!(decoded.relative_offset, Some(-6));
+        assert_eq!(decoded.operand_with_offset(), "$8010 (-6)");
+
+        // LDA #$FA has no relative offset.
+        cpu.memory.save_u8_vector_into_memory(0x8016, vec![0xA9, 0xFA]);
+
+        let decoded = cpu.decode_instruction(0x8016).unwrap();
+
+        assert_eq!(decoded.relative_offset, None);
+        assert_eq!(decoded.operand_with_offset(), "#$FA");
+    }
+
+    #[test]
+    fn test_display_decoded_instruction_and_trace_record() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-                (address as usize, false)
-            }
-            AddressingMode::AbsoluteX => {
-                let address = self
-                    .memory
-                    .get_16_bit_value((self.registers.pc + 1) as usize);
+        // LDA $1234,X
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xBD, 0x34, 0x12]);
 
-                (
-                    address as usize + self.registers.x as usize,
-                    Cpu::crosses_boundary_by_address_offset(address, self.registers.x),
-                )
-            }
-            AddressingMode::AbsoluteY => {
-                let address = self
-                    .memory
-                    .get_16_bit_value((self.registers.pc + 1) as usize);
+        let decoded = cpu.decode_instruction(0x8000).unwrap();
 
-                (
-                    address as usize + self.registers.y as usize,
-                    Cpu::crosses_boundary_by_address_offset(address, self.registers.y),
-                )
-            }
-            AddressingMode::Indirect => {
-                let indirect_address = self
-                    .memory
-                    .get_16_bit_value((self.registers.pc + 1) as usize);
-                let address = self.memory.get_16_bit_value(indirect_address as usize);
+        assert_eq!(decoded.to_string(), cpu.disassemble_opcode(0x8000).unwrap().0);
+        assert_eq!(decoded.to_string(), "8000  BD 34 12 LDA  $1234,X");
 
-                (address as usize, false)
-            }
-            AddressingMode::IndirectX => {
-                let indirect_address = (self
-                    .memory
-                    .get_8_bit_value((self.registers.pc + 1) as usize)
-                    as usize
-                    + self.registers.x as usize)
-                    & 0x00FF;
-                let address = self.memory.get_16_bit_value(indirect_address);
+        let records = Rc::new(RefCell::new(Vec::new()));
+        let recorded = records.clone();
 
-                (address as usize, false)
-            }
-            AddressingMode::IndirectY => {
-                let indirect_address = self
-                    .memory
-                    .get_8_bit_value((self.registers.pc + 1) as usize)
-                    as usize;
-                let address = self.memory.get_16_bit_value(indirect_address);
+        cpu.set_trace_callback(move |record: &TraceRecord| {
+            recorded.borrow_mut().push(record.to_string())
+        });
 
-                (
-                    address as usize + self.registers.y as usize,
-                    Cpu::crosses_boundary_by_address_offset(address, self.registers.y),
-                )
-            }
-        }
-    }
+        cpu.registers.x = 0x01;
+        cpu.execute_instruction().unwrap();
 
-    fn get_instruction_for_opcode(&self, location: usize) -> Option<Instruction> {
-        let opcode = self.memory.get_8_bit_value(location);
+        cpu.memory.contents[0x8003] = 0x9E;
+        cpu.execute_instruction();
 
-        Instruction::binary_search(opcode)
+        assert_eq!(
+            *records.borrow(),
+            vec![
+                "8000  BD 34 12 LDA  $1234,X  A:00 X:01 Y:00 P:24 SP:FF CYC:0".to_string(),
+                "8003           ???           A:00 X:01 Y:00 P:26 SP:FF CYC:4".to_string(),
+            ]
+        );
     }
 
-    fn get_value(&self, instruction: Instruction) -> (u8, bool) {
-        let (address, crossed_boundary) = self.get_address(instruction);
+    #[test]
+    fn test_total_cycles_for_program() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-        (self.memory.get_8_bit_value(address), crossed_boundary)
+        // LDX #$00 (2), LDA $1000,X (4), STA ($10),Y (6), INC $20 (5), BNE $8000 (2), RTS (6)
+        cpu.memory.save_u8_vector_into_memory(
+            0x8000,
+            vec![0xA2, 0x00, 0xBD, 0x00, 0x10, 0x91, 0x10, 0xE6, 0x20, 0xD0, 0xF5, 0x60],
+        );
+
+        assert_eq!(cpu.total_cycles_for_program(0x8000, 0x800B), Some(25));
+        assert_eq!(cpu.total_cycles_for_program(0x8000, 0x8002), Some(6));
+        assert_eq!(cpu.total_cycles_for_program(0x8002, 0x8002), Some(4));
+
+        cpu.memory.contents[0x800C] = 0x9E;
+
+        assert_eq!(cpu.total_cycles_for_program(0x8000, 0x800C), None);
     }
 
-    fn handle_interrupts(&mut self) {
-        let instruction_start_time = Instant::now();
+    #[test]
+    fn test_export_source() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-        self.push_u16(self.registers.pc);
-        self.push_u8(self.registers.p.to_byte());
+        // JSR $800D, LDA $0030, ASL A, BNE $8000, RTS, LAX $30, $9E, INX, then a JMP $1234
+        // cut off by the end of the range.
+        cpu.memory.save_u8_vector_into_memory(
+            0x8000,
+            vec![
+                0x20, 0x0D, 0x80, 0xAD, 0x30, 0x00, 0x0A, 0xD0, 0xF7, 0x60, 0xA7, 0x30, 0x9E,
+                0xE8, 0x4C, 0x34, 0x12,
+            ],
+        );
 
-        self.registers.p.interrupt_disable_flag = true;
+        assert_eq!(
+            cpu.export_source(0x8000, 0x800F),
+            "        .org $8000\n\n\
+             L8000:  jsr L800D\n\
+             \x20       lda a:$0030\n\
+             \x20       asl a\n\
+             \x20       bne L8000\n\
+             \x20       rts\n\
+             \x20       .byte $a7, $30, $9e\n\
+             L800D:  inx\n\
+             \x20       .byte $4c, $34\n"
+        );
 
-        self.registers.pc = match self.nmi_triggered {
-            true => {
-                self.nmi_triggered = false;
-                self.memory.get_16_bit_value(NMI_VECTOR)
-            }
-            false => {
-                self.irq_triggered = false;
-                self.memory.get_16_bit_value(IRQ_BRK_VECTOR)
-            }
-        };
+        cpu.variant = CpuVariant::Wdc65C02;
 
-        let instruction_end_time = Instant::now();
+        // BIT #$01
+        cpu.memory.save_u8_vector_into_memory(0x9000, vec![0x89, 0x01]);
 
-        let elapsed_time = instruction_end_time
-            .duration_since(instruction_start_time)
-            .as_secs_f64();
+        assert_eq!(
+            cpu.export_source(0x9000, 0x9001),
+            "        .setcpu \"65C02\"\n        .org $9000\n\n        bit #$01\n"
+        );
 
-        let target_time = self.cycle_duration * 7.0;
+        // NOP at the top of memory.
+        cpu.memory.contents[0xFFFF] = 0xEA;
 
-        if target_time > elapsed_time {
-            std::thread::sleep(Duration::from_secs_f64(target_time - elapsed_time));
-        }
+        assert!(cpu.export_source(0xFFFF, 0xFFFF).ends_with("\n        nop\n"));
     }
 
-    fn pull_u8(&mut self) -> u8 {
-        self.registers.sp = self.registers.sp.wrapping_add(1);
+    #[test]
+    fn test_disassemble_with_context() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.y = 0x02;
+        cpu.memory.contents[0x0010] = 0x00;
+        cpu.memory.contents[0x0011] = 0x40;
+        cpu.memory.contents[0x4002] = 0x55;
+
+        // LDA ($10),Y, STA $10, LDA #$10, JMP $8000
+        cpu.memory.save_u8_vector_into_memory(
+            0x8000,
+            vec![0xB1, 0x10, 0x85, 0x10, 0xA9, 0x10, 0x4C, 0x00, 0x80],
+        );
 
-        let address = 0x0100 + self.registers.sp as usize;
+        assert_eq!(
+            cpu.disassemble_with_context(0x8000).unwrap(),
+            "8000  B1 10    LDA  ($10),Y  @ $4002 = $55"
+        );
+        assert_eq!(
+            cpu.disassemble_with_context(0x8002).unwrap(),
+            "8002  85 10    STA  $10  = $00"
+        );
+        assert_eq!(
+            cpu.disassemble_with_context(0x8004).unwrap(),
+            cpu.disassemble_opcode(0x8004).unwrap().0
+        );
+        assert_eq!(
+            cpu.disassemble_with_context(0x8006).unwrap(),
+            cpu.disassemble_opcode(0x8006).unwrap().0
+        );
+        assert!(cpu.disassemble_with_context(0x8009).is_some());
 
-        self.memory.get_8_bit_value(address)
+        cpu.memory.contents[0x8009] = 0x9E;
+
+        assert!(cpu.disassemble_with_context(0x8009).is_none());
     }
 
-    fn pull_u16(&mut self) -> u16 {
-        self.registers.sp = self.registers.sp.wrapping_add(2);
+    #[test]
+    fn test_verify_instruction_table() {
+        assert_eq!(Cpu::verify_instruction_table(), Ok(()));
+    }
 
-        let address = 0x0100 + self.registers.sp as usize - 1;
+    #[test]
+    fn test_eval() {
+        // LDA #$05
+        let cpu = Cpu::eval(&[0xA9, 0x05], |_| {});
 
-        self.memory.get_16_bit_value(address)
-    }
+        assert_eq!(cpu.registers.a, 0x05);
+        assert_eq!(cpu.registers.pc, EVAL_ADDRESS + 2);
 
-    fn push_u8(&mut self, value: u8) {
-        let stack_pointer: usize = STACK_BASE_ADDRESS + self.registers.sp as usize;
+        // INX, RTS
+        let cpu = Cpu::eval(&[0xE8, 0x60], |cpu| cpu.registers.x = 0x41);
 
-        self.memory.set_8_bit_value(stack_pointer, value);
+        assert_eq!(cpu.registers.x, 0x42);
+        assert_eq!(cpu.registers.pc, EVAL_ADDRESS + 1);
 
-        self.registers.sp = self.registers.sp.wrapping_sub(1);
+        // JMP to itself
+        let cpu = Cpu::eval(&[0xA0, 0x07, 0x4C, 0x02, 0x02], |_| {});
+
+        assert_eq!(cpu.registers.y, 0x07);
+        assert_eq!(cpu.registers.pc, EVAL_ADDRESS + 2);
     }
 
-    fn push_u16(&mut self, value: u16) {
-        let stack_pointer: usize = STACK_BASE_ADDRESS + self.registers.sp as usize;
+    #[test]
+    fn test_disassemble_lines_wraps_at_top_of_memory() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+
+        // NOP, then a JSR whose operand would run past $FFFF.
+        cpu.memory
+            .save_u8_vector_into_memory(0xFFFD, vec![0xEA, 0x20, 0x00]);
 
-        self.memory.set_16_bit_value(stack_pointer - 1, value);
+        // LDA #$01
+        cpu.memory.save_u8_vector_into_memory(0x0000, vec![0xA9, 0x01]);
 
-        self.registers.sp = self.registers.sp.wrapping_sub(2);
+        assert_eq!(
+            cpu.disassemble_lines(0xFFFD, 3),
+            "FFFD  EA       NOP  \r\n\
+             FFFE  20 00    .BYTE $20,$00\r\n\
+             0000  A9 01    LDA  #$01\r\n"
+        );
+
+        assert_eq!(cpu.decode_instruction(0xFFFE), None);
     }
 
-    fn save_register(&mut self, instruction: Instruction, value: u8) -> ExecutionReturnValues {
-        let (address, _) = self.get_address(instruction);
+    #[test]
+    fn test_with_memory() {
+        let mut memory = Memory::new();
+        memory.save_u8_vector_into_memory(0x8000, vec![0xA9, 0x42]);
 
-        self.memory.set_8_bit_value(address, value);
+        let mut cpu: Cpu = Cpu::with_memory(0x8000, 1_000_000.0, memory);
+        cpu.power_up();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        assert_eq!(cpu.memory.contents[0x8000], 0xA9);
+        assert_eq!(cpu.memory.get_16_bit_value(RESET_VECTOR), 0x8000);
+        assert_eq!(cpu.registers.pc, 0x8000);
 
-    fn set_carry_flag(&mut self, result: u16) {
-        self.registers.p.carry_flag = result > 0xff;
-    }
+        cpu.execute_instruction();
 
-    fn set_negative_flag(&mut self, value: u8) {
-        self.registers.p.negative_flag = value & 0x80 != 0;
+        assert_eq!(cpu.registers.a, 0x42);
     }
 
-    fn set_overflow_flag(&mut self, a: u8, b: u8, result: u8) {
-        // Overflow occurs if both numbers have the same sign and
-        // the result has a different sign.
+    #[test]
+    fn test_undefined_opcodes_as_nops() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        // !(a ^ b) - 0x80 bit will be set if both signs are true.
-        // (a ^ result) - 0x80 bit will be set if result has a different sign.
+        // $9E is undefined and sits in an absolute column.
+        cpu.memory.contents[0x8000] = 0x9E;
 
-        // Based on a StackOverflow answer: https://stackoverflow.com/questions/29193303/6502-emulation-proper-way-to-implement-adc-and-sbc
+        assert!(cpu.execute_instruction().is_none());
+        assert_eq!(cpu.registers.pc, 0x8000);
 
-        self.registers.p.overflow_flag = (!(a ^ b) & (a ^ result) & 0x80) != 0;
+        cpu.undefined_opcodes_as_nops = true;
+
+        let execution_return_values = cpu.execute_instruction().unwrap();
+
+        assert_eq!(execution_return_values.bytes, 3);
+        assert_eq!(execution_return_values.clock_periods, 2);
+        assert_eq!(cpu.registers.pc, 0x8003);
+
+        assert_eq!(Cpu::undefined_opcode_length(0x02), 2);
+        assert_eq!(Cpu::undefined_opcode_length(0x1A), 1);
+        assert_eq!(Cpu::undefined_opcode_length(0x0B), 2);
+        assert_eq!(Cpu::undefined_opcode_length(0x1B), 3);
     }
 
-    fn set_zero_flag(&mut self, value: u8) {
-        self.registers.p.zero_flag = value == 0;
+    #[test]
+    fn test_jam_opcode_halts_the_cpu() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        // LDA #$42, then the JAM opcode $02.
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xA9, 0x42, 0x02]);
+
+        assert_eq!(cpu.run_headless(), RunExit::Jammed(0x8002));
+        assert!(cpu.halted);
+        assert_eq!(cpu.registers.a, 0x42);
+
+        // The cpu stays jammed even if the opcode is replaced.
+        cpu.memory.contents[0x8002] = 0xEA;
+
+        assert!(cpu.execute_instruction().is_none());
+        assert_eq!(cpu.run(None), RunExit::Jammed(0x8002));
+
+        cpu.power_up();
+
+        assert!(!cpu.halted);
+
+        // The WDC 65C02 doesn't jam.
+        cpu.variant = CpuVariant::Wdc65C02;
+        cpu.memory.contents[0x8002] = 0x02;
+
+        assert_eq!(
+            cpu.run_headless(),
+            RunExit::Error(CpuError::UnrecognizedOpcode {
+                opcode: 0x02,
+                address: 0x8002
+            })
+        );
+        assert!(!cpu.halted);
     }
 
-    /***************************************************
-     *
-     * Implementations of the 6502 instructions.
-     *
-     ***************************************************/
+    #[test]
+    fn test_jammed_cpu_ignores_interrupts() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-    fn adc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+        // LDA #$42, then the JAM opcode $02.
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xA9, 0x42, 0x02]);
+        cpu.memory.save_u8_vector_into_memory(0xFFFA, vec![0x00, 0x90]);
 
-        let carry = match self.registers.p.carry_flag {
-            true => 1u16,
-            false => 0u16,
-        };
+        cpu.execute_instruction().unwrap();
 
-        let mut result = self.registers.a as u16 + value as u16 + carry;
+        assert!(cpu.execute_instruction().is_none());
+        assert!(cpu.halted);
 
-        if self.registers.p.decimal_flag {
-            if (self.registers.a & 0x0f) + (value & 0x0F) + carry as u8 > 9 {
-                result += 6;
-            }
+        let sp = cpu.registers.sp;
+        cpu.nmi_triggered = true;
 
-            if result > 0x99 {
-                result += 96;
-            }
-        }
+        assert_eq!(cpu.run_headless(), RunExit::Jammed(0x8002));
+        assert_eq!(cpu.registers.pc, 0x8002);
+        assert_eq!(cpu.registers.sp, sp);
+        assert!(cpu.nmi_triggered);
+    }
 
-        self.set_zero_flag(result as u8);
-        self.set_negative_flag(result as u8);
-        self.set_overflow_flag(self.registers.a, value, result as u8);
-        self.set_carry_flag(result);
+    #[test]
+    fn test_disassemble_bytes() {
+        // LDX #$05, DEX, BNE $0202, then a JMP cut off by the end of the buffer.
+        let lines = disassemble_bytes(&[0xA2, 0x05, 0xCA, 0xD0, 0xFD, 0x4C], 0x0200);
 
-        self.registers.a = result as u8;
+        assert_eq!(
+            lines,
+            vec![
+                ("0200  A2 05    LDX  #$05".to_string(), 2),
+                ("0202  CA       DEX  ".to_string(), 1),
+                ("0203  D0 FD    BNE  $0202".to_string(), 2),
+                ("0205  4C       .BYTE $4C".to_string(), 1),
+            ]
+        );
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(
+            disassemble_bytes(&[0x9E], 0x0200),
+            vec![("0200 9E        UNRECONIZED OPCODE".to_string(), 1)]
+        );
     }
 
-    fn and_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+    #[test]
+    fn test_disassemble_accumulator_shifts() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-        let result = self.registers.a & value;
+        // ASL A, ROL A, LSR A, ROR A
+        let bytes = [0x0A, 0x2A, 0x4A, 0x6A];
+        cpu.memory.save_u8_vector_into_memory(0x8000, bytes.to_vec());
 
-        self.set_zero_flag(result);
-        self.set_negative_flag(result);
+        let expected = vec![
+            ("8000  0A       ASL  A".to_string(), 1),
+            ("8001  2A       ROL  A".to_string(), 1),
+            ("8002  4A       LSR  A".to_string(), 1),
+            ("8003  6A       ROR  A".to_string(), 1),
+        ];
 
-        self.registers.a = result;
+        let lines: Vec<(String, u8)> = (0x8000..0x8004)
+            .map(|address| cpu.disassemble_opcode(address).unwrap())
+            .collect();
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(lines, expected);
+        assert_eq!(disassemble_bytes(&bytes, 0x8000), expected);
     }
 
-    fn asl_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (address, value, crossed_boundary): (Option<usize>, u8, bool) =
-            match instruction.addressing_mode {
-                AddressingMode::Accumulator => (None, self.registers.a, false),
-                _ => {
-                    let (address, crossed_boundary) = self.get_address(instruction);
-                    (
-                        Some(address),
-                        self.memory.contents[address],
-                        crossed_boundary,
-                    )
-                }
-            };
+    #[test]
+    fn test_run_headless() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        self.registers.p.carry_flag = value & 0x80 == 0x80;
+        // LDA #$01, BEQ $8002, BNE $8004
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA9, 0x01, 0xF0, 0xFE, 0xD0, 0xFE]);
 
-        let result = value << 1;
+        assert_eq!(cpu.run_headless(), RunExit::Trapped(0x8004));
+        assert_eq!(cpu.total_instructions(), 3);
 
-        self.set_zero_flag(result);
-        self.set_negative_flag(result);
+        cpu.registers.pc = 0x8000;
+        cpu.success_address = Some(0x8004);
 
-        if address.is_none() {
-            self.registers.a = result;
-        } else {
-            self.memory.contents[address.unwrap()] = result;
-        }
+        assert_eq!(cpu.run_headless(), RunExit::Success);
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        cpu.memory.contents[0x8000] = 0x9E;
+
+        assert_eq!(
+            cpu.run_headless(),
+            RunExit::Error(CpuError::UnrecognizedOpcode {
+                opcode: 0x9E,
+                address: 0x8000
+            })
+        );
     }
 
-    fn bcc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.branch(instruction, !self.registers.p.carry_flag)
+    #[test]
+    fn test_max_call_depth() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        // JSR $8010, JSR $8020, then RTS at $8010 and JSR $8020 at $8020.
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x20, 0x10, 0x80, 0x20, 0x20, 0x80]);
+        cpu.memory.contents[0x8010] = 0x60;
+        cpu.memory
+            .save_u8_vector_into_memory(0x8020, vec![0x20, 0x20, 0x80]);
+
+        cpu.step_until_cycles(12).unwrap();
+
+        assert_eq!(cpu.call_depth(), 0);
+        assert_eq!(cpu.registers.pc, 0x8003);
+
+        // The JSR $8020 recurses forever.
+        cpu.max_call_depth = Some(100);
+
+        assert_eq!(cpu.run_headless(), RunExit::RecursionLimit(0x8020));
+        assert_eq!(cpu.call_depth(), 101);
+
+        cpu.power_up();
+        cpu.max_call_depth = Some(3);
+
+        assert_eq!(
+            cpu.run(Some(|_: &str| "X".to_string())),
+            RunExit::RecursionLimit(0x8020)
+        );
+        assert_eq!(cpu.call_depth(), 4);
     }
 
-    fn bcs_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.branch(instruction, self.registers.p.carry_flag)
+    #[test]
+    fn test_pushed_status_bytes() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+        cpu.poke_u16(IRQ_BRK_VECTOR as u16, 0x9000);
+        cpu.memory.contents[0x8000] = 0x08;
+        cpu.memory.contents[0x8001] = 0x00;
+
+        // A break flag left set by PLP must not leak into the IRQ's pushed status.
+        cpu.registers.p.from_byte(StatusFlags::BREAK_FLAG | StatusFlags::CARRY_FLAG);
+        cpu.irq_triggered = true;
+        cpu.poll_interrupts();
+
+        assert_eq!(
+            cpu.memory.contents[0x01FD],
+            StatusFlags::UNUSED_FLAG | StatusFlags::CARRY_FLAG
+        );
+
+        let flags = StatusFlags::UNUSED_FLAG | StatusFlags::BREAK_FLAG | StatusFlags::CARRY_FLAG;
+
+        // PHP
+        cpu.registers.pc = 0x8000;
+        cpu.registers.p.from_byte(StatusFlags::CARRY_FLAG);
+        cpu.execute_instruction().unwrap();
+
+        assert_eq!(cpu.memory.contents[0x01FC], flags);
+
+        // BRK
+        cpu.execute_instruction().unwrap();
+
+        assert_eq!(cpu.memory.contents[0x01F9], flags);
     }
 
-    fn beq_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.branch(instruction, self.registers.p.zero_flag)
+    #[test]
+    fn test_shared_memory() {
+        let shared = Rc::new(RefCell::new(vec![0u8; 0x0100]));
+
+        let mut writer: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        writer.power_up();
+        writer.memory.add_shared_region(0x2000, 0x20FF, shared.clone());
+
+        // LDA #$42, STA $2010
+        writer
+            .memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA9, 0x42, 0x8D, 0x10, 0x20]);
+
+        let mut reader: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        reader.power_up();
+        reader.memory.add_shared_region(0x2000, 0x20FF, shared.clone());
+
+        // LDX $2010
+        reader
+            .memory
+            .save_u8_vector_into_memory(0x8000, vec![0xAE, 0x10, 0x20]);
+
+        writer.step_until_cycles(6).unwrap();
+        reader.step_until_cycles(4).unwrap();
+
+        assert_eq!(reader.registers.x, 0x42);
+        assert_eq!(shared.borrow()[0x10], 0x42);
+        assert_eq!(writer.memory.contents[0x2010], 0x00);
     }
 
-    fn bit_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, _) = self.get_value(instruction);
+    #[test]
+    fn test_indirect_pointers_wrap_within_zero_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.memory.contents[0x00FF] = 0x00;
+        cpu.memory.contents[0x0000] = 0x30;
+        cpu.memory.contents[0x0100] = 0x40;
+        cpu.memory.contents[0x3000] = 0x11;
+        cpu.memory.contents[0x3002] = 0x22;
 
-        let result = self.registers.a & value;
+        // LDA ($F0,X) with X = $0F reads its pointer from $FF and $00.
+        cpu.registers.x = 0x0F;
+        cpu.registers.pc = 0x8000;
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xA1, 0xF0]);
+        cpu.execute_opcode().unwrap();
 
-        self.set_zero_flag(result);
-        self.registers.p.negative_flag = value & 0x80 != 0;
-        self.registers.p.overflow_flag = value & 0x40 != 0;
+        assert_eq!(cpu.registers.a, 0x11);
+
+        // LDA ($FF),Y
+        cpu.registers.y = 0x02;
+        cpu.registers.pc = 0x8000;
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xB1, 0xFF]);
+        cpu.execute_opcode().unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
+        assert_eq!(cpu.registers.a, 0x22);
     }
 
-    fn bmi_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.branch(instruction, self.registers.p.negative_flag)
+    #[test]
+    fn test_indirect_y_page_cross_cycles() {
+        // Reads pay a cycle when ($10),Y crosses a page, but STA always takes 6.
+        let cases: [(u8, &str, u8, u8); 9] = [
+            (0x11, "ORA", 5, 6),
+            (0x31, "AND", 5, 6),
+            (0x51, "EOR", 5, 6),
+            (0x71, "ADC", 5, 6),
+            (0x91, "STA", 6, 6),
+            (0xB1, "LDA", 5, 6),
+            (0xB3, "LAX", 5, 6),
+            (0xD1, "CMP", 5, 6),
+            (0xF1, "SBC", 5, 6),
+        ];
+
+        for (opcode, mnemonic, same_page, crossing_page) in cases {
+            let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+            cpu.memory.contents[0x0010] = 0x80;
+            cpu.memory.contents[0x0011] = 0x30;
+            cpu.memory.save_u8_vector_into_memory(0x8000, vec![opcode, 0x10]);
+
+            for (y, expected) in [(0x7F, same_page), (0x80, crossing_page)] {
+                cpu.registers.y = y;
+                cpu.registers.pc = 0x8000;
+
+                let return_values = cpu.execute_opcode().unwrap();
+
+                assert_eq!(
+                    return_values.clock_periods, expected,
+                    "{} with Y = ${:02X}",
+                    mnemonic, y
+                );
+            }
+        }
     }
 
-    fn bne_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.branch(instruction, !self.registers.p.zero_flag)
+    #[test]
+    fn test_read_modify_write_absolute_x_cycles() {
+        // Read-modify-write instructions always take 7 cycles with $3080,X, whether or
+        // not indexing crosses a page.
+        let cases: [(u8, &str); 12] = [
+            (0x1E, "ASL"),
+            (0x1F, "SLO"),
+            (0x3E, "ROL"),
+            (0x3F, "RLA"),
+            (0x5E, "LSR"),
+            (0x5F, "SRE"),
+            (0x7E, "ROR"),
+            (0x7F, "RRA"),
+            (0xDE, "DEC"),
+            (0xDF, "DCP"),
+            (0xFE, "INC"),
+            (0xFF, "ISC"),
+        ];
+
+        for (opcode, mnemonic) in cases {
+            let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+            cpu.memory.save_u8_vector_into_memory(0x8000, vec![opcode, 0x80, 0x30]);
+
+            for x in [0x7F, 0x80] {
+                cpu.registers.x = x;
+                cpu.registers.pc = 0x8000;
+
+                let return_values = cpu.execute_opcode().unwrap();
+
+                assert_eq!(return_values.clock_periods, 7, "{} with X = ${:02X}", mnemonic, x);
+            }
+        }
     }
 
-    fn bpl_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.branch(instruction, !self.registers.p.negative_flag)
-    }
+    #[test]
+    fn test_record_and_replay() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+        cpu.registers.p.interrupt_disable_flag = false;
 
-    fn brk_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.push_u16(self.registers.pc + 2);
+        // INX, JMP $8000
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xE8, 0x4C, 0x00, 0x80]);
 
-        self.registers.p.break_flag = true;
-        self.push_u8(self.registers.p.to_byte());
-        self.registers.p.break_flag = false;
+        // The NMI handler increments $10 and the IRQ handler increments $11.
+        cpu.memory
+            .save_u8_vector_into_memory(0x9000, vec![0xE6, 0x10, 0x40]);
+        cpu.memory
+            .save_u8_vector_into_memory(0x9100, vec![0xE6, 0x11, 0x58, 0x40]);
+        cpu.poke_u16(NMI_VECTOR as u16, 0x9000);
+        cpu.poke_u16(IRQ_BRK_VECTOR as u16, 0x9100);
 
-        self.registers.p.interrupt_disable_flag = true;
+        let start = cpu.record();
 
-        self.registers.pc = self.memory.get_16_bit_value(IRQ_BRK_VECTOR);
+        cpu.step_until_cycles(50).unwrap();
+        cpu.nmi_triggered = true;
+        cpu.step_until_cycles(100).unwrap();
+        cpu.set_rdy(false);
+        cpu.step_until_cycles(110).unwrap();
+        cpu.set_rdy(true);
+        cpu.irq_triggered = true;
+        cpu.step_until_cycles(200).unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        let log = cpu.stop_recording().unwrap();
+        let end = cpu.snapshot();
 
-    fn bvc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.branch(instruction, !self.registers.p.overflow_flag)
-    }
+        assert_eq!(cpu.memory.contents[0x10], 1);
+        assert!(cpu.memory.contents[0x11] > 0);
+        assert_eq!(
+            log.events.iter().map(|event| event.input).collect::<Vec<Input>>(),
+            vec![
+                Input::Nmi(true),
+                Input::Rdy(false),
+                Input::Rdy(true),
+                Input::Irq(true)
+            ]
+        );
 
-    fn bvs_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.branch(instruction, self.registers.p.overflow_flag)
-    }
+        let mut replayed: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-    fn clc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.p.carry_flag = false;
+        replayed.replay(&start, log.clone());
+        replayed.step_until_cycles(200).unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        assert_eq!(replayed.snapshot(), end);
 
-    fn cld_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.p.decimal_flag = false;
+        // Without the log the run diverges.
+        replayed.restore(&start);
+        replayed.step_until_cycles(200).unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
+        assert_ne!(replayed.snapshot(), end);
     }
 
-    fn cli_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.p.interrupt_disable_flag = false;
+    #[test]
+    fn test_step_over() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        // JSR $9000, INX
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x20, 0x00, 0x90, 0xE8]);
 
-    fn clv_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.p.overflow_flag = false;
+        // JSR $9100, RTS, then LDY #$02, RTS at $9100.
+        cpu.memory
+            .save_u8_vector_into_memory(0x9000, vec![0x20, 0x00, 0x91, 0x60]);
+        cpu.memory
+            .save_u8_vector_into_memory(0x9100, vec![0xA0, 0x02, 0x60]);
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        let return_values = cpu.step_over().unwrap();
 
-    fn cmp_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(cpu.registers.pc, 0x8003);
+        assert_eq!(cpu.registers.sp, 0xFF);
+        assert_eq!(cpu.registers.y, 0x02);
+        assert_eq!(cpu.total_instructions(), 5);
 
-        self.compare(self.registers.a, value);
+        // INX is stepped like any other instruction.
+        let return_values = cpu.step_over().unwrap();
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(return_values.bytes, 1);
+        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(cpu.registers.pc, 0x8004);
+        assert_eq!(cpu.registers.x, 0x01);
+        assert_eq!(cpu.total_instructions(), 6);
     }
 
-    fn cpx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+    #[test]
+    fn test_step_over_a_subroutine_that_traps() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        self.compare(self.registers.x, value);
+        // JSR $9000, then JMP $9000 at $9000.
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x20, 0x00, 0x90]);
+        cpu.memory
+            .save_u8_vector_into_memory(0x9000, vec![0x4C, 0x00, 0x90]);
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(
+            cpu.step_over().unwrap_err(),
+            CpuError::Trapped { address: 0x9000 }
+        );
+        assert_eq!(cpu.registers.pc, 0x9000);
     }
 
-    fn cpy_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+    #[test]
+    fn test_step_out() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        self.compare(self.registers.y, value);
+        // JSR $9000, INX
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x20, 0x00, 0x90, 0xE8]);
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
-    }
+        // JSR $9100, LDA #$01, RTS, then LDY #$02, RTS at $9100.
+        cpu.memory
+            .save_u8_vector_into_memory(0x9000, vec![0x20, 0x00, 0x91, 0xA9, 0x01, 0x60]);
+        cpu.memory
+            .save_u8_vector_into_memory(0x9100, vec![0xA0, 0x02, 0x60]);
 
-    fn dec_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (address, crossed_boundary) = self.get_address(instruction);
+        cpu.execute_instruction().unwrap();
 
-        let result = self.memory.contents[address].wrapping_sub(1);
+        assert_eq!(cpu.registers.pc, 0x9000);
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        cpu.step_out().unwrap();
 
-        self.memory.contents[address] = result;
+        assert_eq!(cpu.registers.pc, 0x8003);
+        assert_eq!(cpu.registers.sp, 0xFF);
+        assert_eq!(cpu.registers.a, 0x01);
+        assert_eq!(cpu.registers.y, 0x02);
+        assert_eq!(cpu.registers.x, 0x00);
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        // Back at the top level there's no return address to step out to.
+        assert_eq!(
+            cpu.step_out(),
+            Err(CpuError::NoReturnAddress { address: 0x8003 })
+        );
+        assert_eq!(cpu.registers.pc, 0x8003);
+        assert_eq!(cpu.registers.x, 0x00);
     }
 
-    fn dex_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let result = self.registers.x.wrapping_sub(1);
+    #[test]
+    fn test_mock_clock() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000.0);
+        cpu.power_up();
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        let clock = MockClock::new();
+        cpu.set_clock(clock.clone());
 
-        self.registers.x = result;
+        // JMP $8000
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x4C, 0x00, 0x80]);
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        let start = std::time::Instant::now();
 
-    fn dey_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let result = self.registers.y.wrapping_sub(1);
+        // A second of virtual time at 1KHz.
+        cpu.run_frames(100, 10, |_: &mut Cpu| {}).unwrap();
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        assert!((clock.elapsed().as_secs_f64() - 1.0).abs() < 0.000_001);
+        assert!(start.elapsed().as_secs_f64() < 0.5);
 
-        self.registers.y = result;
+        // An interrupt's 7 cycles are throttled too.
+        cpu.poke_u16(NMI_VECTOR as u16, 0x8000);
+        cpu.nmi_triggered = true;
+        cpu.step_until_cycles(cpu.cycles() + 1).unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
+        assert!((clock.elapsed().as_secs_f64() - 1.007).abs() < 0.000_001);
     }
 
-    fn eor_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
-
-        let result = self.registers.a ^ value;
+    #[test]
+    fn test_clear_registers_and_memory() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        // LDA #$80, PHA
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA9, 0x80, 0x48]);
+        cpu.step_until_cycles(5).unwrap();
+        cpu.irq_triggered = true;
 
-        self.registers.a = result;
+        cpu.clear_registers();
+        cpu.memory.clear();
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(cpu.registers.to_string(), Registers::new().to_string());
+        assert_eq!(cpu.cycles(), 0);
+        assert_eq!(cpu.instructions, 0);
+        assert!(!cpu.irq_triggered);
+        assert!(cpu.memory.contents.iter().all(|&byte| byte == 0x00));
     }
 
-    fn inc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (address, crossed_boundary) = self.get_address(instruction);
+    #[test]
+    fn test_reset() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        let result = self.memory.contents[address].wrapping_add(1);
+        // LDA #$80, PHA, LDX #$42
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA9, 0x80, 0x48, 0xA2, 0x42]);
+        cpu.step_until_cycles(7).unwrap();
+        cpu.registers.p.interrupt_disable_flag = false;
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        assert_eq!(cpu.registers.sp, 0xFE);
 
-        self.memory.contents[address] = result;
+        cpu.reset();
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(cpu.registers.pc, 0x8000);
+        assert_eq!(cpu.registers.sp, 0xFB);
+        assert!(cpu.registers.p.interrupt_disable_flag);
+        assert_eq!(cpu.registers.a, 0x80);
+        assert_eq!(cpu.registers.x, 0x42);
+        assert_eq!(cpu.cycles(), 14);
+        assert_eq!(cpu.memory.contents[0x01FF], 0x80);
     }
 
-    fn inx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let result = self.registers.x.wrapping_add(1);
+    #[test]
+    fn test_prepare_test() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        cpu.prepare_test(0x8000, 0xC3, 0x11, 0x22, 0x33, 0xF0);
 
-        self.registers.x = result;
+        assert_eq!(cpu.registers.pc, 0x8000);
+        assert_eq!(cpu.registers.p.to_byte(), 0xE3);
+        assert_eq!(cpu.registers.a, 0x11);
+        assert_eq!(cpu.registers.x, 0x22);
+        assert_eq!(cpu.registers.y, 0x33);
+        assert_eq!(cpu.registers.sp, 0xF0);
 
-        ExecutionReturnValues::new(instruction, false)
+        // LDA #$44
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xA9, 0x44]);
+
+        cpu.execute_opcode();
+
+        assert_eq!(cpu.registers.a, 0x44);
+        assert!(!cpu.registers.p.negative_flag);
     }
 
-    fn iny_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let result = self.registers.y.wrapping_add(1);
+    #[test]
+    fn test_load_and_save_breakpoints() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        let file_path = std::env::temp_dir().join("rust_6502_test_load_and_save_breakpoints.txt");
+        let file_path = file_path.to_str().unwrap();
 
-        self.registers.y = result;
+        std::fs::write(file_path, "# Main loop\n$8010\n\n$c000 # Reset handler\n").unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        let result = cpu.load_breakpoints(file_path);
 
-    fn jmp_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (address, _) = self.get_address(instruction);
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(cpu.breakpoints, vec![0x8010, 0xC000]);
 
-        self.registers.pc = address as u16;
+        cpu.toggle_breakpoint(0x8010);
+        cpu.save_breakpoints(file_path).unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        let saved = std::fs::read_to_string(file_path);
 
-    fn jsr_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (address, _) = self.get_address(instruction);
+        std::fs::write(file_path, "$8010\nC000\n").unwrap();
 
-        self.push_u16(self.registers.pc + 2);
+        let result = cpu.load_breakpoints(file_path);
 
-        self.registers.pc = address as u16;
+        std::fs::remove_file(file_path).unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
+        assert_eq!(saved.unwrap(), "$C000\n");
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(cpu.breakpoints, vec![0xC000]);
     }
 
-    fn lda_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+    #[test]
+    fn test_load_program_at_reset() {
+        let mut cpu: Cpu = Cpu::new(0x2000, 1_000_000.0);
 
-        self.set_zero_flag(value);
-        self.set_negative_flag(value);
+        let file_path = std::env::temp_dir().join("rust_6502_test_load_program_at_reset.bin");
+        std::fs::write(&file_path, [0xA9, 0x01, 0xEA]).unwrap();
 
-        self.registers.a = value;
+        let result = cpu.load_program_at_reset(file_path.to_str().unwrap());
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        std::fs::remove_file(&file_path).unwrap();
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(cpu.memory.contents[0x2000..0x2003], [0xA9, 0x01, 0xEA]);
     }
 
-    fn ldx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+    #[test]
+    fn test_poke_and_peek_u16() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-        self.set_negative_flag(value);
-        self.set_zero_flag(value);
+        cpu.poke_u16(0x1000, 0xBEEF);
 
-        self.registers.x = value;
+        assert_eq!(cpu.memory.contents[0x1000], 0xEF);
+        assert_eq!(cpu.memory.contents[0x1001], 0xBE);
+        assert_eq!(cpu.peek_u16(0x1000), 0xBEEF);
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
-    }
+        cpu.poke_u16(0xFFFF, 0x1234);
 
-    fn ldy_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+        assert_eq!(cpu.memory.contents[0x0000], 0x12);
+        assert_eq!(cpu.peek_u16(0xFFFF), 0x1234);
+    }
 
-        self.set_negative_flag(value);
-        self.set_zero_flag(value);
+    #[test]
+    fn test_poke_many() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        self.registers.y = value;
+        // LDA $10, CLC, ADC $11
+        cpu.poke_many(&[
+            (0x8000, 0xA5),
+            (0x8001, 0x10),
+            (0x8002, 0x18),
+            (0x8003, 0x65),
+            (0x8004, 0x11),
+            (0x0010, 0x20),
+            (0x0011, 0x22),
+        ]);
+
+        for _ in 0..3 {
+            cpu.execute_instruction();
+        }
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(cpu.registers.a, 0x42);
+        assert_eq!(cpu.registers.pc, 0x8005);
     }
 
-    fn lsr_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (address, value, crossed_boundary): (Option<usize>, u8, bool) =
-            match instruction.addressing_mode {
-                AddressingMode::Accumulator => (None, self.registers.a, false),
-                _ => {
-                    let (address, crossed_boundary) = self.get_address(instruction);
-                    (
-                        Some(address),
-                        self.memory.contents[address],
-                        crossed_boundary,
-                    )
-                }
-            };
+    #[test]
+    fn test_assert_memory() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-        self.registers.p.carry_flag = value & 0x01 == 0x01;
+        cpu.poke_many(&[(0x1000, 0x01), (0x1001, 0x02), (0x1002, 0x03)]);
 
-        let result = (value >> 1) & 0x7F;
+        cpu.assert_memory(0x1000, &[0x01, 0x02, 0x03]);
+    }
 
-        self.registers.p.negative_flag = false;
-        self.set_zero_flag(result);
+    #[test]
+    #[should_panic(expected = "Memory starting at 1000 doesn't match:\n  1001: expected FF, found 02")]
+    fn test_assert_memory_reports_mismatches() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
 
-        if address.is_none() {
-            self.registers.a = result;
-        } else {
-            self.memory.contents[address.unwrap()] = result;
-        }
+        cpu.poke_many(&[(0x1000, 0x01), (0x1001, 0x02), (0x1002, 0x03)]);
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        cpu.assert_memory(0x1000, &[0x01, 0xFF, 0x03]);
     }
 
-    fn nop_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        ExecutionReturnValues::new(instruction, false)
-    }
+    #[test]
+    fn test_exec_hooks() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-    fn ora_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+        // NOP, NOP, TAX
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xEA, 0xEA, 0xAA]);
 
-        let result = self.registers.a | value;
+        let mnemonics = Rc::new(RefCell::new(Vec::new()));
+        let pre_exec_mnemonics = mnemonics.clone();
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        cpu.set_pre_exec_hook(move |cpu: &mut Cpu, decoded: &DecodedInstruction| {
+            assert_eq!(cpu.registers.pc, decoded.address);
+            pre_exec_mnemonics.borrow_mut().push(decoded.mnemonic);
+        });
+        cpu.set_post_exec_hook(|cpu: &mut Cpu, _: &DecodedInstruction| cpu.registers.a += 1);
 
-        self.registers.a = result;
+        for _ in 0..3 {
+            cpu.execute_instruction();
+        }
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
-    }
+        assert_eq!(*mnemonics.borrow(), vec!["NOP", "NOP", "TAX"]);
+        assert_eq!(cpu.registers.a, 0x03);
+        assert_eq!(cpu.registers.x, 0x02);
 
-    fn pha_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.push_u8(self.registers.a);
+        cpu.clear_exec_hooks();
+        cpu.registers.pc = 0x8000;
+        cpu.execute_instruction();
 
-        ExecutionReturnValues::new(instruction, false)
+        assert_eq!(cpu.registers.a, 0x03);
     }
 
-    fn php_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let mut flags = self.registers.p.to_byte();
-        flags |= StatusFlags::UNUSED_FLAG | StatusFlags::BREAK_FLAG;
+    #[test]
+    fn test_trace_filter() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        self.push_u8(flags);
+        // JSR $9000, NOP
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x20, 0x00, 0x90, 0xEA]);
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        // INX, RTS
+        cpu.memory.save_u8_vector_into_memory(0x9000, vec![0xE8, 0x60]);
 
-    fn pla_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.a = self.pull_u8();
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        let records = trace.clone();
 
-        self.set_negative_flag(self.registers.a);
-        self.set_zero_flag(self.registers.a);
+        cpu.set_trace_callback(move |record: &TraceRecord| records.borrow_mut().push(record.clone()));
+        cpu.set_trace_filter(0x9000..=0x9FFF);
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        for _ in 0..4 {
+            cpu.execute_instruction();
+        }
 
-    fn plp_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let mut flags = self.pull_u8();
+        let addresses: Vec<u16> = trace.borrow().iter().map(|record| record.pc).collect();
 
-        flags &= !StatusFlags::BREAK_FLAG;
+        assert_eq!(addresses, vec![0x9000, 0x9001]);
+        assert_eq!(trace.borrow()[0].instruction.as_ref().unwrap().mnemonic, "INX");
 
-        self.registers.p.from_byte(flags);
+        cpu.clear_trace_filter();
+        cpu.registers.pc = 0x8000;
+        cpu.execute_instruction();
 
-        ExecutionReturnValues::new(instruction, false)
+        assert_eq!(trace.borrow().last().unwrap().pc, 0x8000);
     }
 
-    fn rol_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (address, value, crossed_boundary): (Option<usize>, u8, bool) =
-            match instruction.addressing_mode {
-                AddressingMode::Accumulator => (None, self.registers.a, false),
-                _ => {
-                    let (address, crossed_boundary) = self.get_address(instruction);
-                    (
-                        Some(address),
-                        self.memory.contents[address],
-                        crossed_boundary,
-                    )
-                }
-            };
+    #[test]
+    fn test_rdy_stalls_the_cpu() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        let high_bit = value & 0x80;
+        // INX
+        cpu.memory.contents[0x8000] = 0xE8;
 
-        let mut result = value << 1;
+        cpu.add_peripheral(Box::new(CountdownTimer { remaining: 3 }));
+        cpu.registers.p.interrupt_disable_flag = false;
+        cpu.memory.set_16_bit_value(IRQ_BRK_VECTOR, 0x4000);
 
-        if self.registers.p.carry_flag {
-            result |= 0x01;
+        cpu.set_rdy(false);
+
+        for _ in 0..5 {
+            cpu.poll_interrupts();
+            cpu.execute_instruction();
         }
 
-        self.registers.p.carry_flag = high_bit == 0x80;
+        assert_eq!(cpu.registers.pc, 0x8000);
+        assert_eq!(cpu.registers.x, 0x00);
+        assert_eq!(cpu.cycles(), 5);
+        assert_eq!(cpu.total_instructions(), 0);
+        assert!(cpu.irq_triggered);
 
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        cpu.set_rdy(true);
 
-        if address.is_none() {
-            self.registers.a = result;
-        } else {
-            self.memory.contents[address.unwrap()] = result;
-        }
+        cpu.poll_interrupts();
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(cpu.registers.pc, 0x4000);
     }
 
-    fn ror_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (address, value, crossed_boundary): (Option<usize>, u8, bool) =
-            match instruction.addressing_mode {
-                AddressingMode::Accumulator => (None, self.registers.a, false),
-                _ => {
-                    let (address, crossed_boundary) = self.get_address(instruction);
-                    (
-                        Some(address),
-                        self.memory.contents[address],
-                        crossed_boundary,
-                    )
-                }
-            };
-
-        let low_bit = value & 0x01;
+    #[test]
+    fn test_total_instructions() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        let mut result = value >> 1;
+        // LDX #$05, DEX, BNE $8002
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA2, 0x05, 0xCA, 0xD0, 0xFD]);
 
-        if self.registers.p.carry_flag {
-            result |= 0x80;
+        while cpu.registers.pc != 0x8005 {
+            cpu.execute_instruction();
         }
 
-        self.registers.p.carry_flag = low_bit == 0x01;
-
-        self.set_negative_flag(result);
-        self.set_zero_flag(result);
+        assert_eq!(cpu.total_instructions(), 11);
 
-        if address.is_none() {
-            self.registers.a = result;
-        } else {
-            self.memory.contents[address.unwrap()] = result;
-        }
+        cpu.reset_total_instructions();
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(cpu.total_instructions(), 0);
     }
 
-    fn rti_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let mut flags = self.pull_u8();
+    #[test]
+    fn test_step() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        flags &= !StatusFlags::BREAK_FLAG;
+        // LDX #$01, LDA $10FF,X, BEQ $8007, NOP, then the undefined opcode $9E.
+        cpu.memory.save_u8_vector_into_memory(
+            0x8000,
+            vec![0xA2, 0x01, 0xBD, 0xFF, 0x10, 0xF0, 0x00, 0xEA, 0x9E],
+        );
 
-        self.registers.p.from_byte(flags);
+        let cycles: Vec<u8> = (0..4).map(|_| cpu.step().unwrap()).collect();
 
-        let address = self.pull_u16();
+        assert_eq!(cycles, vec![2, 5, 3, 2]);
+        assert_eq!(cpu.cycles(), 12);
+        assert_eq!(cpu.registers.pc, 0x8008);
+        assert_eq!(cpu.step(), None);
+    }
 
-        self.registers.pc = address;
+    #[test]
+    fn test_step_until_cycles() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        // LDA $1000 (4 cycles), JMP $8000 (3 cycles)
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xAD, 0x00, 0x10, 0x4C, 0x00, 0x80]);
 
-    fn rts_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let address = self.pull_u16();
+        assert_eq!(cpu.step_until_cycles(5), Ok(7));
+        assert_eq!(cpu.registers.pc, 0x8000);
 
-        self.registers.pc = address + 1;
+        assert_eq!(cpu.step_until_cycles(11), Ok(11));
+        assert_eq!(cpu.registers.pc, 0x8003);
 
-        ExecutionReturnValues::new(instruction, false)
+        assert_eq!(cpu.step_until_cycles(11), Ok(11));
     }
 
-    fn sbc_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        let (value, crossed_boundary) = self.get_value(instruction);
+    #[test]
+    fn test_unrecognized_opcode_returns_an_error() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        let carry = match self.registers.p.carry_flag {
-            true => 0u16,
-            false => 1u16,
-        };
+        // NOP, then the undefined opcode $9E.
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xEA, 0x9E]);
 
-        let mut result = (self.registers.a as u16).wrapping_sub(value as u16 + carry);
+        let error = CpuError::UnrecognizedOpcode {
+            opcode: 0x9E,
+            address: 0x8001,
+        };
 
-        if self.registers.p.decimal_flag {
-            if (self.registers.a & 0x0f) < (value & 0x0f) + carry as u8 {
-                result -= 6;
-            }
+        assert_eq!(cpu.run(None), RunExit::Error(error));
 
-            if result > 0x99 {
-                result -= 96;
-            }
-        }
+        cpu.registers.pc = 0x8000;
 
-        self.set_zero_flag(result as u8);
-        self.set_negative_flag(result as u8);
-        self.set_overflow_flag(self.registers.a, !value, result as u8);
-        self.set_carry_flag(!result);
+        assert_eq!(cpu.run_frames(100, 1, |_| {}), Err(error));
 
-        self.registers.a = result as u8;
+        cpu.registers.pc = 0x8000;
 
-        ExecutionReturnValues::new(instruction, crossed_boundary)
+        assert_eq!(cpu.step_until_cycles(cpu.cycles() + 100), Err(error));
+        assert_eq!(error.to_string(), "Unrecognized opcode: 9E @ 8001");
     }
 
-    fn sec_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.p.carry_flag = true;
+    #[test]
+    fn test_addresses_wrap_at_the_top_of_memory() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.x = 0x02;
+        cpu.memory.contents[0x0001] = 0x42;
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        // LDA $FFFF,X
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xBD, 0xFF, 0xFF]);
 
-    fn sed_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.p.decimal_flag = true;
+        cpu.registers.pc = 0x8000;
+        cpu.execute_instruction();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        assert_eq!(cpu.registers.a, 0x42);
 
-    fn sei_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.p.interrupt_disable_flag = true;
+        // NOP at the top of memory.
+        cpu.memory.contents[0xFFFF] = 0xEA;
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        cpu.registers.pc = 0xFFFF;
+        cpu.execute_instruction();
 
-    fn sta_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.save_register(instruction, self.registers.a)
+        assert_eq!(cpu.registers.pc, 0x0000);
     }
 
-    fn stx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.save_register(instruction, self.registers.x)
-    }
+    #[test]
+    fn test_run_frames() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-    fn sty_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.save_register(instruction, self.registers.y)
-    }
+        // NOP, NOP, JMP $8000
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xEA, 0xEA, 0x4C, 0x00, 0x80]);
 
-    fn tax_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.x = self.registers.a;
+        let mut frame_cycles: Vec<u64> = Vec::new();
 
-        self.set_negative_flag(self.registers.x);
-        self.set_zero_flag(self.registers.x);
+        cpu.run_frames(100, 3, |cpu: &mut Cpu| frame_cycles.push(cpu.cycles()))
+            .unwrap();
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        assert_eq!(frame_cycles.len(), 3);
 
-    fn tay_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.y = self.registers.a;
+        for (frame, &cycles) in frame_cycles.iter().enumerate() {
+            let budget = (frame as u64 + 1) * 100;
 
-        self.set_negative_flag(self.registers.y);
-        self.set_zero_flag(self.registers.y);
+            assert!(cycles >= budget && cycles < budget + 3);
+        }
+    }
 
-        ExecutionReturnValues::new(instruction, false)
+    struct CountdownTimer {
+        remaining: u64,
     }
 
-    fn tsx_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.x = self.registers.sp;
+    impl Peripheral for CountdownTimer {
+        fn tick(&mut self, cycles: u8, _: &mut Memory) -> Option<InterruptKind> {
+            if self.remaining == 0 {
+                return None;
+            }
 
-        self.set_negative_flag(self.registers.x);
-        self.set_zero_flag(self.registers.x);
+            self.remaining = self.remaining.saturating_sub(cycles as u64);
 
-        ExecutionReturnValues::new(instruction, false)
+            match self.remaining {
+                0 => Some(InterruptKind::Irq),
+                _ => None,
+            }
+        }
     }
 
-    fn txa_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.a = self.registers.x;
+    #[test]
+    fn test_peripheral_raises_irq() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
 
-        self.set_negative_flag(self.registers.a);
-        self.set_zero_flag(self.registers.a);
+        cpu.memory.set_16_bit_value(IRQ_BRK_VECTOR, 0x4000);
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        // CLI, NOP, JMP $8001
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0x58, 0xEA, 0x4C, 0x01, 0x80]);
 
-    fn txs_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.sp = self.registers.x;
+        cpu.add_peripheral(Box::new(CountdownTimer { remaining: 20 }));
 
-        ExecutionReturnValues::new(instruction, false)
-    }
+        while cpu.registers.pc != 0x4000 {
+            cpu.poll_interrupts();
 
-    fn tya_instruction(&mut self, instruction: Instruction) -> ExecutionReturnValues {
-        self.registers.a = self.registers.y;
+            if cpu.registers.pc != 0x4000 {
+                cpu.execute_instruction();
+            }
 
-        self.set_negative_flag(self.registers.a);
-        self.set_zero_flag(self.registers.a);
+            assert!(cpu.cycles() < 40);
+        }
 
-        ExecutionReturnValues::new(instruction, false)
+        assert!(cpu.cycles() >= 20 + 7);
+        assert!(cpu.registers.p.interrupt_disable_flag);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_set_zero_flag_when_not_zero() {
+    fn test_snapshot_and_restore() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.p.zero_flag = true;
+        cpu.prepare_test(0x8000, 0x81, 0x11, 0x22, 0x33, 0xF0);
+        cpu.memory.contents[0x1234] = 0x56;
 
-        cpu.set_zero_flag(0xff);
+        let snapshot = cpu.snapshot();
 
-        assert!(!cpu.registers.p.zero_flag);
+        cpu.prepare_test(0x9000, 0x00, 0x00, 0x00, 0x00, 0xFF);
+        cpu.memory.contents[0x1234] = 0x00;
+
+        cpu.restore(&snapshot);
+
+        assert_eq!(cpu.snapshot(), snapshot);
+        assert_eq!(cpu.registers.pc, 0x8000);
+        assert_eq!(cpu.memory.contents[0x1234], 0x56);
     }
 
     #[test]
-    fn test_set_zero_flag_when_zero() {
+    fn test_registers_snapshot_and_restore() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.p.zero_flag = false;
+        cpu.prepare_test(0x8000, 0x81, 0x11, 0x22, 0x33, 0xF0);
+        cpu.memory.contents[0x1234] = 0x56;
 
-        cpu.set_zero_flag(0x00);
+        let snapshot = cpu.registers_snapshot();
 
-        assert!(cpu.registers.p.zero_flag);
+        // LDA #$00
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xA9, 0x00]);
+        cpu.step_until_cycles(2).unwrap();
+        cpu.registers.sp = 0xFF;
+        cpu.memory.contents[0x1234] = 0x00;
+
+        cpu.restore_registers(&snapshot);
+
+        assert_eq!(cpu.registers_snapshot(), snapshot);
+        assert_eq!(cpu.registers.a, 0x11);
+        assert!(cpu.registers.p.negative_flag && !cpu.registers.p.zero_flag);
+        assert_eq!(cpu.cycles(), 0);
+        assert_eq!(cpu.memory.contents[0x1234], 0x00);
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_set_overflow_flag_when_two_positives_results_in_a_negative() {
+    fn test_save_and_load_state() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.p.overflow_flag = false;
+        cpu.prepare_test(0x8000, 0x81, 0x11, 0x22, 0x33, 0xF0);
+        cpu.memory.contents[0x1234] = 0x56;
 
-        cpu.set_overflow_flag(0x7f, 0x01, (0x7f + 0x01) as u16 as u8);
+        let file_path = std::env::temp_dir().join("rust_6502_test_save_and_load_state.json");
+        let file_path = file_path.to_str().unwrap();
 
-        assert!(cpu.registers.p.overflow_flag);
+        cpu.save_state(file_path).unwrap();
+
+        let mut loaded_cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        let result = loaded_cpu.load_state(file_path);
+
+        std::fs::remove_file(file_path).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(loaded_cpu.snapshot(), cpu.snapshot());
     }
 
     #[test]
-    fn test_set_overflow_flag_when_two_positives_results_in_a_positive() {
+    fn test_run_pauses_and_resumes() {
+        thread_local! {
+            static COMMANDS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn debugger(_: &str) -> String {
+            COMMANDS.with(|commands| commands.borrow_mut().remove(0).to_string())
+        }
+
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.p.overflow_flag = false;
+        cpu.power_up();
 
-        cpu.set_overflow_flag(0x7e, 0x01, (0x7e + 0x01) as u16 as u8);
+        // INX, INX, INX
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xE8, 0xE8, 0xE8]);
 
-        assert!(!cpu.registers.p.overflow_flag);
+        COMMANDS.with(|commands| *commands.borrow_mut() = vec!["T", "S", "P"]);
+
+        assert_eq!(cpu.run(Some(debugger)), RunExit::Paused(0x8001));
+        assert_eq!(cpu.registers.x, 0x01);
+        assert!(!cpu.debug_trapping);
+
+        COMMANDS.with(|commands| *commands.borrow_mut() = vec!["S", "Q"]);
+
+        assert_eq!(cpu.run(Some(debugger)), RunExit::Quit);
+        assert_eq!(cpu.registers.x, 0x02);
+        assert_eq!(cpu.registers.pc, 0x8002);
+        assert!(cpu.debug_trapping);
     }
 
     #[test]
-    fn test_set_overflow_flag_when_two_negatives_results_in_a_positive() {
-        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.p.overflow_flag = false;
+    fn test_trap_threshold() {
+        thread_local! {
+            static TRAPPED: Cell<bool> = const { Cell::new(false) };
+        }
 
-        cpu.set_overflow_flag(0x80, 0xff, (0x80 + 0xff) as u16 as u8);
+        fn debugger(output: &str) -> String {
+            match output.contains("Trap encountered") {
+                true => {
+                    TRAPPED.with(|trapped| trapped.set(true));
+                    "Q".to_string()
+                }
+                false => "X".to_string(),
+            }
+        }
 
-        assert!(cpu.registers.p.overflow_flag);
+        for threshold in [1, 4] {
+            let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+            cpu.power_up();
+            cpu.trap_threshold = threshold;
+
+            // BVC $8000, polling for the overflow flag.
+            cpu.memory.save_u8_vector_into_memory(0x8000, vec![0x50, 0xFE]);
+
+            TRAPPED.with(|trapped| trapped.set(false));
+
+            cpu.run(Some(debugger));
+
+            assert!(TRAPPED.with(|trapped| trapped.get()));
+            assert_eq!(cpu.total_instructions(), threshold as u64);
+        }
     }
 
     #[test]
-    fn test_set_overflow_flag_when_two_negatives_results_in_a_negative() {
+    fn test_debugger_history() {
+        thread_local! {
+            static COMMANDS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+            static OUTPUTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn debugger(output: &str) -> String {
+            OUTPUTS.with(|outputs| outputs.borrow_mut().push(output.to_string()));
+            COMMANDS.with(|commands| commands.borrow_mut().remove(0).to_string())
+        }
+
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.p.overflow_flag = false;
+        cpu.power_up();
 
-        cpu.set_overflow_flag(0x81, 0xff, (0x81 + 0xff) as u16 as u8);
+        COMMANDS.with(|commands| *commands.borrow_mut() = vec!["H", "t", "D $1000", "H", "Q"]);
 
-        assert!(!cpu.registers.p.overflow_flag);
+        cpu.run(Some(debugger));
+
+        OUTPUTS.with(|outputs| {
+            let outputs = outputs.borrow();
+
+            assert_eq!(outputs[1], "No commands entered.");
+            assert_eq!(outputs[4], " 1  t\r\n 2  D $1000\r\n");
+        });
     }
 
     #[test]
-    fn test_set_carry_flag_when_no_carry() {
-        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.p.carry_flag = true;
+    fn test_debugger_quits_on_closed_input() {
+        thread_local! {
+            static PROMPTS: Cell<u32> = const { Cell::new(0) };
+        }
 
-        cpu.set_carry_flag(0x00ff);
+        fn debugger(_output: &str) -> String {
+            PROMPTS.with(|prompts| prompts.set(prompts.get() + 1));
+            String::new()
+        }
 
-        assert!(!cpu.registers.p.carry_flag);
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        assert_eq!(cpu.run(Some(debugger)), RunExit::Quit);
+        assert_eq!(PROMPTS.with(|prompts| prompts.get()), 1);
     }
 
     #[test]
-    fn test_set_carry_flag_when_carry() {
+    fn test_debugger_verbose() {
+        thread_local! {
+            static COMMANDS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+            static OUTPUTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn debugger(output: &str) -> String {
+            OUTPUTS.with(|outputs| outputs.borrow_mut().push(output.to_string()));
+            COMMANDS.with(|commands| commands.borrow_mut().remove(0).to_string())
+        }
+
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.p.carry_flag = false;
+        cpu.power_up();
+        cpu.debugger_verbose = false;
 
-        cpu.set_carry_flag(0x0100);
+        // NOP, NOP
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xEA, 0xEA]);
 
-        assert!(cpu.registers.p.carry_flag);
-    }
+        COMMANDS.with(|commands| *commands.borrow_mut() = vec!["S", "V", "S", "Q"]);
 
-    #[test]
-    fn test_crosses_boundary_not_crossed() {
-        assert!(!Cpu::crosses_boundary_by_address_offset(0x1ffe, 0x01));
+        cpu.run(Some(debugger));
+
+        OUTPUTS.with(|outputs| {
+            let outputs = outputs.borrow();
+
+            assert_eq!(outputs[0], "");
+            assert_eq!(outputs[1], "");
+            assert_eq!(outputs[2], "Verbose display is enabled.");
+            assert!(outputs[3].contains("8002  00       BRK"));
+        });
+
+        assert!(cpu.debugger_verbose);
     }
 
     #[test]
-    fn test_crosses_boundary_crossed() {
-        assert!(Cpu::crosses_boundary_by_address_offset(0x1fff, 0x01));
+    fn test_debugger_banner_shows_cycles() {
+        thread_local! {
+            static COMMANDS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+            static OUTPUTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn debugger(output: &str) -> String {
+            OUTPUTS.with(|outputs| outputs.borrow_mut().push(output.to_string()));
+            COMMANDS.with(|commands| commands.borrow_mut().remove(0).to_string())
+        }
+
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        // LDA #$01, LDA $1000
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA9, 0x01, 0xAD, 0x00, 0x10]);
+
+        COMMANDS.with(|commands| *commands.borrow_mut() = vec!["S", "S", "Q"]);
+
+        cpu.run(Some(debugger));
+
+        OUTPUTS.with(|outputs| {
+            let outputs = outputs.borrow();
+
+            assert!(outputs[0].contains("\r\nCYC:0\r\n"));
+            assert!(outputs[1].contains("\r\nCYC:2\r\n"));
+            assert!(outputs[2].contains("\r\nCYC:6\r\n"));
+        });
     }
 
     #[test]
-    fn test_pull_u8() {
+    fn test_debugger_step_count() {
+        thread_local! {
+            static COMMANDS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+            static OUTPUTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn debugger(output: &str) -> String {
+            OUTPUTS.with(|outputs| outputs.borrow_mut().push(output.to_string()));
+            COMMANDS.with(|commands| commands.borrow_mut().remove(0).to_string())
+        }
+
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.sp = 0xFE;
-        cpu.registers.pc = 0x8000;
+        cpu.power_up();
 
-        cpu.memory.contents[0x01FF] = 0xFF;
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0xEA; 8]);
 
-        let result = cpu.pull_u8();
+        COMMANDS.with(|commands| *commands.borrow_mut() = vec!["S 5", "S", "Q"]);
 
-        assert_eq!(cpu.registers.sp, 0xFF);
-        assert_eq!(result, 0xFF);
+        assert_eq!(cpu.run(Some(debugger)), RunExit::Quit);
+
+        OUTPUTS.with(|outputs| {
+            let outputs = outputs.borrow();
+
+            assert_eq!(outputs.len(), 3);
+            assert!(outputs[1].contains("\r\n8005 00"));
+            assert!(outputs[2].contains("\r\n8006 00"));
+        });
+
+        assert_eq!(cpu.total_instructions(), 6);
     }
 
     #[test]
-    fn test_pull_u16() {
+    fn test_break_on_flag_change() {
+        thread_local! {
+            static COMMANDS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+            static OUTPUTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn debugger(output: &str) -> String {
+            OUTPUTS.with(|outputs| outputs.borrow_mut().push(output.to_string()));
+            COMMANDS.with(|commands| commands.borrow_mut().remove(0).to_string())
+        }
+
+        let hook_calls = Rc::new(Cell::new(0));
+        let calls = Rc::clone(&hook_calls);
+
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.sp = 0xFD;
-        cpu.registers.pc = 0x8000;
+        cpu.power_up();
+        cpu.set_post_exec_hook(move |_, _| calls.set(calls.get() + 1));
+        cpu.break_on_flag_change(Flag::Zero);
 
-        cpu.memory.contents[0x01FE] = 0xFF;
-        cpu.memory.contents[0x01FF] = 0x20;
+        // LDA #$01, LDX #$01, LDA #$00, NOP
+        cpu.memory
+            .save_u8_vector_into_memory(0x8000, vec![0xA9, 0x01, 0xA2, 0x01, 0xA9, 0x00, 0xEA]);
 
-        let result = cpu.pull_u16();
+        COMMANDS.with(|commands| *commands.borrow_mut() = vec!["X", "Q"]);
 
-        assert_eq!(cpu.registers.sp, 0xFF);
-        assert_eq!(result, 0x20FF);
-    }
+        assert_eq!(cpu.run(Some(debugger)), RunExit::Quit);
 
-    #[test]
-    fn test_push_u8() {
-        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.sp = 0xff;
-        cpu.registers.pc = 0x8000;
+        OUTPUTS.with(|outputs| {
+            let outputs = outputs.borrow();
 
-        cpu.memory.contents[0x01ff] = 0x00;
+            // The zero flag only changes when LDA #$00 loads zero.
+            assert_eq!(outputs.len(), 2);
+            assert!(outputs[1].contains("\r\n8006 00"));
+        });
 
-        cpu.push_u8(0xff);
+        assert!(cpu.registers.p.zero_flag);
 
-        assert_eq!(cpu.registers.sp, 0xfe);
-        assert_eq!(cpu.memory.contents[0x01ff], 0xff);
+        // The post-exec hook still runs alongside the watch.
+        assert_eq!(hook_calls.get(), 3);
     }
 
     #[test]
-    fn test_push_u16() {
+    fn test_debug_display_memory() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.sp = 0xff;
-        cpu.registers.pc = 0x8000;
 
-        cpu.memory.contents[0x01fe] = 0x00;
-        cpu.memory.contents[0x01ff] = 0x00;
-        cpu.push_u16(0x0102);
+        for i in 0..0x20 {
+            cpu.memory.contents[0x1000 + i] = i as u8;
+        }
 
-        assert_eq!(cpu.registers.sp, 0xfd);
-        assert_eq!(cpu.memory.contents[0x01fe], 0x02);
-        assert_eq!(cpu.memory.contents[0x01ff], 0x01);
+        assert_eq!(
+            cpu.debug_display_memory(0x1000, 18),
+            "1000: 00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F\r\n1010: 10 11"
+        );
+
+        assert_eq!(cpu.debug_display_memory(0xFFFF, 2), "FFFF: 00 00");
     }
 
     #[test]
-    fn test_compare_when_register_is_less_than_value() {
+    fn test_debugger_display_stack() {
+        thread_local! {
+            static COMMANDS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+            static OUTPUTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+        }
+
+        fn debugger(output: &str) -> String {
+            OUTPUTS.with(|outputs| outputs.borrow_mut().push(output.to_string()));
+            COMMANDS.with(|commands| commands.borrow_mut().remove(0).to_string())
+        }
+
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.a = 0x10;
-        cpu.registers.p.negative_flag = false;
-        cpu.registers.p.zero_flag = true;
-        cpu.registers.p.carry_flag = true;
+        cpu.power_up();
+        cpu.debugger_verbose = false;
 
-        cpu.compare(cpu.registers.a, 0x11);
+        assert_eq!(cpu.debug_display_stack(), "The stack is empty.");
 
-        assert!(cpu.registers.p.negative_flag);
-        assert!(!cpu.registers.p.zero_flag);
-        assert!(!cpu.registers.p.carry_flag);
-    }
+        cpu.push_u8(0x11);
+        cpu.push_u8(0x22);
+        cpu.push_u8(0x33);
 
-    #[test]
-    fn test_compare_when_register_is_equal_to_value() {
-        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.a = 0x10;
-        cpu.registers.p.negative_flag = true;
-        cpu.registers.p.zero_flag = false;
-        cpu.registers.p.carry_flag = false;
+        COMMANDS.with(|commands| *commands.borrow_mut() = vec!["K", "Q"]);
 
-        cpu.compare(cpu.registers.a, 0x10);
+        cpu.run(Some(debugger));
 
-        assert!(!cpu.registers.p.negative_flag);
-        assert!(cpu.registers.p.zero_flag);
-        assert!(cpu.registers.p.carry_flag);
+        OUTPUTS.with(|outputs| {
+            assert_eq!(outputs.borrow()[1], "Top of stack @ 01FD\r\n01FD: 33 22 11");
+        });
     }
 
     #[test]
-    fn test_compare_when_register_is_greater_than_value() {
-        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.a = 0x10;
-        cpu.registers.p.negative_flag = true;
-        cpu.registers.p.zero_flag = true;
-        cpu.registers.p.carry_flag = false;
+    fn test_debugger_help_lists_every_command() {
+        let help = Cpu::debugger_help();
 
-        cpu.compare(cpu.registers.a, 0x09);
+        let lines: Vec<&str> = help.split("\r\n").collect();
 
-        assert!(!cpu.registers.p.negative_flag);
-        assert!(!cpu.registers.p.zero_flag);
-        assert!(cpu.registers.p.carry_flag);
+        for command in ["B", "D", "K", "S", "T", "X", "P", "V", "H", "Q", "?"] {
+            assert!(
+                lines.iter().any(|line| line.split(' ').next() == Some(command)),
+                "{} is missing from the help",
+                command
+            );
+        }
+
+        assert!(help.contains("T       - Toggle Trapping\r\n"));
     }
 
     #[test]
@@ -1461,6 +5732,69 @@ mod tests {
         assert!(return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_ricoh_2a03_ignores_decimal_flag() {
+        // SED, CLC, LDA #$09, ADC #$01, SEC, SBC #$01
+        let program = [0xF8, 0x18, 0xA9, 0x09, 0x69, 0x01, 0x38, 0xE9, 0x01];
+
+        for (variant, sum, difference) in [
+            (CpuVariant::Nmos6502, 0x10, 0x09),
+            (CpuVariant::Ricoh2A03, 0x0A, 0x09),
+        ] {
+            let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+            cpu.power_up();
+            cpu.variant = variant;
+            cpu.memory.save_u8_vector_into_memory(0x8000, program.to_vec());
+
+            for _ in 0..4 {
+                cpu.execute_instruction().unwrap();
+            }
+
+            assert!(cpu.is_flag_set(Flag::Decimal));
+            assert_eq!(cpu.registers.a, sum);
+
+            cpu.execute_instruction().unwrap();
+            cpu.execute_instruction().unwrap();
+
+            assert_eq!(cpu.registers.a, difference);
+        }
+    }
+
+    #[test]
+    fn test_00_brk_timing_and_decimal_flag() {
+        for variant in [CpuVariant::Nmos6502, CpuVariant::Wdc65C02] {
+            let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+            cpu.power_up();
+            cpu.variant = variant;
+            cpu.registers.p.decimal_flag = true;
+            cpu.registers.p.interrupt_disable_flag = false;
+
+            cpu.memory.contents[0x8000] = 0x00;
+            cpu.poke_u16(IRQ_BRK_VECTOR as u16, 0x4000);
+
+            cpu.execute_instruction().unwrap();
+
+            assert_eq!(cpu.cycles(), 7);
+            assert_eq!(cpu.total_instructions(), 1);
+            assert_eq!(cpu.registers.pc, 0x4000);
+            assert_eq!(cpu.registers.sp, 0xFC);
+
+            // The return address skips the signature byte and the pushed status has B set.
+            assert_eq!(cpu.peek_u16(0x01FE), 0x8002);
+            assert_eq!(
+                cpu.memory.contents[0x01FD],
+                StatusFlags::UNUSED_FLAG | StatusFlags::BREAK_FLAG | StatusFlags::DECIMAL_FLAG
+            );
+
+            assert!(cpu.is_flag_set(Flag::InterruptDisable));
+            assert!(!cpu.is_flag_set(Flag::Break));
+            assert_eq!(
+                cpu.is_flag_set(Flag::Decimal),
+                variant == CpuVariant::Nmos6502
+            );
+        }
+    }
+
     #[test]
     fn test_01_ora_indirect_x_instruction_carry() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1490,6 +5824,38 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_03_slo_indirect_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x01;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x0033] = 0x40;
+        cpu.memory.contents[0x4000] = 0x81;
+        cpu.memory.contents[0x8000] = 0x03;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4000], 0x02);
+        assert_eq!(cpu.registers.a, 0x03);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_05_ora_zero_page_instruction_carry() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1544,6 +5910,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_07_slo_zero_page_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x01;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0x40;
+        cpu.memory.contents[0x8000] = 0x07;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0030], 0x80);
+        assert_eq!(cpu.registers.a, 0x81);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_08_php_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1646,6 +6041,32 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_0b_anc_immediate_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xC1;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x0B;
+        cpu.memory.contents[0x8001] = 0x81;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0x81);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_0d_ora_absolute_instruction_carry() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1701,6 +6122,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_0f_slo_absolute_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3000] = 0x80;
+        cpu.memory.contents[0x8000] = 0x0F;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3000], 0x00);
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_10_bpl_relative_instruction_with_negative_set() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1739,7 +6190,7 @@ mod tests {
 
         assert_eq!(cpu.registers.pc, 0x8004);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 3);
         assert!(return_values.set_program_counter);
     }
 
@@ -1772,6 +6223,38 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_13_slo_indirect_y_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x01;
+        cpu.registers.y = 0x10;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0xF0;
+        cpu.memory.contents[0x0031] = 0x40;
+        cpu.memory.contents[0x4100] = 0x81;
+        cpu.memory.contents[0x8000] = 0x13;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4100], 0x02);
+        assert_eq!(cpu.registers.a, 0x03);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_15_ora_zero_page_x_instruction_carry() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1793,23 +6276,52 @@ mod tests {
 
         assert_eq!(cpu.registers.a, 0x77);
         assert!(!cpu.registers.p.zero_flag);
-        assert!(!cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 4);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_16_asl_zero_page_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.x = 0x02;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0xcc;
+        cpu.memory.contents[0x8000] = 0x16;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0032], 0x98);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert!(cpu.registers.p.carry_flag);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 4);
+        assert_eq!(return_values.clock_periods, 6);
         assert!(!return_values.set_program_counter);
     }
 
     #[test]
-    fn test_16_asl_zero_page_x_instruction() {
+    fn test_17_slo_zero_page_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x01;
         cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = true;
         cpu.registers.p.zero_flag = true;
-        cpu.registers.p.negative_flag = true;
-        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
         cpu.registers.pc = 0x8000;
 
-        cpu.memory.contents[0x0032] = 0xcc;
-        cpu.memory.contents[0x8000] = 0x16;
+        cpu.memory.contents[0x0032] = 0x40;
+        cpu.memory.contents[0x8000] = 0x17;
         cpu.memory.contents[0x8001] = 0x30;
 
         let option_return_values = cpu.execute_opcode();
@@ -1818,10 +6330,11 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert_eq!(cpu.memory.contents[0x0032], 0x98);
+        assert_eq!(cpu.memory.contents[0x0032], 0x80);
+        assert_eq!(cpu.registers.a, 0x81);
+        assert!(!cpu.registers.p.carry_flag);
         assert!(!cpu.registers.p.zero_flag);
         assert!(cpu.registers.p.negative_flag);
-        assert!(cpu.registers.p.carry_flag);
         assert_eq!(return_values.bytes, 2);
         assert_eq!(return_values.clock_periods, 6);
         assert!(!return_values.set_program_counter);
@@ -1841,7 +6354,7 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.is_flag_set(Flag::Carry));
         assert_eq!(return_values.bytes, 1);
         assert_eq!(return_values.clock_periods, 2);
         assert!(!return_values.set_program_counter);
@@ -1875,6 +6388,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_1b_slo_absolute_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3002] = 0x80;
+        cpu.memory.contents[0x8000] = 0x1B;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3002], 0x00);
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_1d_ora_absolute_x_instruction_carry() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1932,6 +6476,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_1f_slo_absolute_x_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x01;
+        cpu.registers.x = 0x10;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3100] = 0x81;
+        cpu.memory.contents[0x8000] = 0x1F;
+        cpu.memory.contents[0x8001] = 0xF0;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3100], 0x02);
+        assert_eq!(cpu.registers.a, 0x03);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_20_jsr_absolute_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1957,6 +6532,41 @@ mod tests {
         assert!(return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_20_jsr_absolute_instruction_at_top_of_memory() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.sp = 0xFF;
+        cpu.registers.pc = 0xFFFE;
+
+        // JSR $3000 with its operand wrapping to $0000, then RTS at $3000.
+        cpu.memory.contents[0xFFFE] = 0x20;
+        cpu.memory.contents[0xFFFF] = 0x00;
+        cpu.memory.contents[0x0000] = 0x30;
+        cpu.memory.contents[0x3000] = 0x60;
+
+        cpu.execute_opcode();
+
+        // The pushed return address, $FFFE + 2, wraps to $0000.
+        assert_eq!(cpu.registers.pc, 0x3000);
+        assert_eq!(cpu.memory.contents[0x01FE], 0x00);
+        assert_eq!(cpu.memory.contents[0x01FF], 0x00);
+
+        cpu.execute_opcode();
+
+        assert_eq!(cpu.registers.pc, 0x0001);
+
+        // An RTS that pulls $FFFF returns to $0000.
+        cpu.registers.sp = 0xFD;
+        cpu.registers.pc = 0x3000;
+
+        cpu.memory.contents[0x01FE] = 0xFF;
+        cpu.memory.contents[0x01FF] = 0xFF;
+
+        cpu.execute_opcode();
+
+        assert_eq!(cpu.registers.pc, 0x0000);
+    }
+
     #[test]
     fn test_21_and_indirect_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -1987,6 +6597,38 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_23_rla_indirect_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x0033] = 0x40;
+        cpu.memory.contents[0x4000] = 0x40;
+        cpu.memory.contents[0x8000] = 0x23;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4000], 0x81);
+        assert_eq!(cpu.registers.a, 0x81);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_24_bit_zero_page_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2015,6 +6657,79 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_24_bit_flag_outcomes() {
+        // (A, M, zero, negative, overflow)
+        let cases = [
+            (0x01, 0x01, false, false, false),
+            (0x01, 0x02, true, false, false),
+            (0x80, 0x81, false, true, false),
+            (0x01, 0x41, false, false, true),
+            (0x00, 0xC0, true, true, true),
+            (0xFF, 0xBF, false, true, false),
+        ];
+
+        for (a, value, zero, negative, overflow) in cases {
+            let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+            cpu.registers.a = a;
+            cpu.registers.p.zero_flag = !zero;
+            cpu.registers.p.negative_flag = !negative;
+            cpu.registers.p.overflow_flag = !overflow;
+            cpu.registers.pc = 0x8000;
+
+            cpu.memory.contents[0x0030] = value;
+            cpu.memory.contents[0x8000] = 0x24;
+            cpu.memory.contents[0x8001] = 0x30;
+
+            cpu.execute_opcode().unwrap();
+
+            assert_eq!(cpu.registers.a, a);
+            assert_eq!(cpu.is_flag_set(Flag::Zero), zero, "Z for {:02X} & {:02X}", a, value);
+            assert_eq!(cpu.is_flag_set(Flag::Negative), negative, "N for {:02X}", value);
+            assert_eq!(cpu.is_flag_set(Flag::Overflow), overflow, "V for {:02X}", value);
+        }
+    }
+
+    #[test]
+    fn test_89_bit_immediate_instruction_65c02() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.variant = CpuVariant::Wdc65C02;
+        cpu.registers.a = 0xFF;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x89;
+        cpu.memory.contents[0x8001] = 0x00;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert!(cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+
+        // BIT $zp with the same operand clears N and V.
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x24;
+        cpu.memory.contents[0x8001] = 0x30;
+        cpu.memory.contents[0x0030] = 0x00;
+
+        cpu.execute_opcode();
+
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+    }
+
     #[test]
     fn test_25_and_zero_page_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2068,6 +6783,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_27_rla_zero_page_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0x80;
+        cpu.memory.contents[0x8000] = 0x27;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0030], 0x00);
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_28_plp_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2168,6 +6912,32 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_2b_anc_immediate_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x7F;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x2B;
+        cpu.memory.contents[0x8001] = 0x0F;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0x0F);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_2c_bit_absolute_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2252,6 +7022,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_2f_rla_absolute_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x01;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3000] = 0x81;
+        cpu.memory.contents[0x8000] = 0x2F;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3000], 0x03);
+        assert_eq!(cpu.registers.a, 0x01);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_30_bmi_relative_instruction_with_negative_not_set() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2290,7 +7090,7 @@ mod tests {
 
         assert_eq!(cpu.registers.pc, 0x8004);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 3);
         assert!(return_values.set_program_counter);
     }
 
@@ -2320,7 +7120,39 @@ mod tests {
         assert!(!cpu.registers.p.zero_flag);
         assert!(cpu.registers.p.negative_flag);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 5);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_33_rla_indirect_y_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.y = 0x10;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0xF0;
+        cpu.memory.contents[0x0031] = 0x40;
+        cpu.memory.contents[0x4100] = 0x40;
+        cpu.memory.contents[0x8000] = 0x33;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4100], 0x81);
+        assert_eq!(cpu.registers.a, 0x81);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
         assert!(!return_values.set_program_counter);
     }
 
@@ -2378,6 +7210,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_37_rla_zero_page_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x80;
+        cpu.memory.contents[0x8000] = 0x37;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0032], 0x00);
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_38_sec_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2426,6 +7288,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_3b_rla_absolute_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x01;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3002] = 0x81;
+        cpu.memory.contents[0x8000] = 0x3B;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3002], 0x03);
+        assert_eq!(cpu.registers.a, 0x01);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_3d_and_absolute_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2483,6 +7376,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_3f_rla_absolute_x_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.x = 0x10;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3100] = 0x80;
+        cpu.memory.contents[0x8000] = 0x3F;
+        cpu.memory.contents[0x8001] = 0xF0;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3100], 0x00);
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_40_rti_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2538,6 +7462,38 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_43_sre_indirect_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x0033] = 0x40;
+        cpu.memory.contents[0x4000] = 0x03;
+        cpu.memory.contents[0x8000] = 0x43;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4000], 0x01);
+        assert_eq!(cpu.registers.a, 0xFE);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_45_eor_zero_page_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2591,6 +7547,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_47_sre_zero_page_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0x80;
+        cpu.memory.contents[0x8000] = 0x47;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0030], 0x40);
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_48_pha_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2665,6 +7650,33 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_4b_alr_immediate_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x4B;
+        cpu.memory.contents[0x8001] = 0x03;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0x01);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_4c_jmp_absolute_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2741,6 +7753,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_4f_sre_absolute_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x0F;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3000] = 0xF1;
+        cpu.memory.contents[0x8000] = 0x4F;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3000], 0x78);
+        assert_eq!(cpu.registers.a, 0x77);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_50_bvc_relative_instruction_with_overflow_not_set() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2758,7 +7800,7 @@ mod tests {
 
         assert_eq!(cpu.registers.pc, 0x8004);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 3);
         assert!(return_values.set_program_counter);
     }
 
@@ -2812,6 +7854,38 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_53_sre_indirect_y_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.y = 0x10;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0xF0;
+        cpu.memory.contents[0x0031] = 0x40;
+        cpu.memory.contents[0x4100] = 0x03;
+        cpu.memory.contents[0x8000] = 0x53;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4100], 0x01);
+        assert_eq!(cpu.registers.a, 0xFE);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_55_eor_zero_page_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2867,10 +7941,41 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_57_sre_zero_page_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x80;
+        cpu.memory.contents[0x8000] = 0x57;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0032], 0x40);
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_58_cli_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
         cpu.registers.p.interrupt_disable_flag = true;
+        cpu.registers.p.carry_flag = true;
         cpu.registers.pc = 0x8000;
 
         cpu.memory.contents[0x8000] = 0x58;
@@ -2881,23 +7986,53 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.is_flag_set(Flag::InterruptDisable));
+        assert!(cpu.is_flag_set(Flag::Carry));
         assert_eq!(return_values.bytes, 1);
         assert_eq!(return_values.clock_periods, 2);
         assert!(!return_values.set_program_counter);
     }
 
     #[test]
-    fn test_59_eor_absolute_y_instruction() {
+    fn test_59_eor_absolute_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xCC;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3002] = 0xEE;
+        cpu.memory.contents[0x8000] = 0x59;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0x22);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 4);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_5b_sre_absolute_y_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.a = 0xCC;
+        cpu.registers.a = 0x0F;
         cpu.registers.y = 0x02;
+        cpu.registers.p.carry_flag = false;
         cpu.registers.p.zero_flag = true;
         cpu.registers.p.negative_flag = true;
         cpu.registers.pc = 0x8000;
 
-        cpu.memory.contents[0x3002] = 0xEE;
-        cpu.memory.contents[0x8000] = 0x59;
+        cpu.memory.contents[0x3002] = 0xF1;
+        cpu.memory.contents[0x8000] = 0x5B;
         cpu.memory.contents[0x8001] = 0x00;
         cpu.memory.contents[0x8002] = 0x30;
 
@@ -2907,11 +8042,13 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert_eq!(cpu.registers.a, 0x22);
+        assert_eq!(cpu.memory.contents[0x3002], 0x78);
+        assert_eq!(cpu.registers.a, 0x77);
+        assert!(cpu.registers.p.carry_flag);
         assert!(!cpu.registers.p.zero_flag);
         assert!(!cpu.registers.p.negative_flag);
         assert_eq!(return_values.bytes, 3);
-        assert_eq!(return_values.clock_periods, 4);
+        assert_eq!(return_values.clock_periods, 7);
         assert!(!return_values.set_program_counter);
     }
 
@@ -2972,6 +8109,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_5f_sre_absolute_x_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.x = 0x10;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3100] = 0x03;
+        cpu.memory.contents[0x8000] = 0x5F;
+        cpu.memory.contents[0x8001] = 0xF0;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3100], 0x01);
+        assert_eq!(cpu.registers.a, 0xFE);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_60_rts_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -2995,6 +8163,24 @@ mod tests {
         assert!(return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_60_rts_implied_instruction_wraps_the_stack() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.sp = 0xFE;
+        cpu.registers.pc = 0x8000;
+
+        // The return address is pulled from $01FF and then $0100, not $00FF.
+        cpu.memory.contents[0x00FF] = 0x12;
+        cpu.memory.contents[0x01FF] = 0x03;
+        cpu.memory.contents[0x0100] = 0x30;
+        cpu.memory.contents[0x8000] = 0x60;
+
+        cpu.execute_opcode();
+
+        assert_eq!(cpu.registers.pc, 0x3004);
+        assert_eq!(cpu.registers.sp, 0x00);
+    }
+
     #[test]
     fn test_61_adc_indirect_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3028,6 +8214,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_63_rra_indirect_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x10;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x0033] = 0x40;
+        cpu.memory.contents[0x4000] = 0x01;
+        cpu.memory.contents[0x8000] = 0x63;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4000], 0x00);
+        assert_eq!(cpu.registers.a, 0x11);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_65_adc_zero_page_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3085,6 +8302,34 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_67_rra_zero_page_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.overflow_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0x80;
+        cpu.memory.contents[0x8000] = 0x67;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0030], 0x40);
+        assert_eq!(cpu.registers.a, 0x80);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_68_pla_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3344,6 +8589,57 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_69_adc_immediate_instruction_decimal_flags() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x99;
+        cpu.registers.p.decimal_flag = true;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x69;
+        cpu.memory.contents[0x8001] = 0x01; // 99 + 01 = 00 with a carry.
+
+        cpu.execute_opcode();
+
+        // The NMOS 6502 sets Z from the binary sum, $9A, and N from $A0, the sum after
+        // the low nibble is corrected.
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+
+        cpu.registers.a = 0x79;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8001] = 0x00; // 79 + 00 + 1 = 80.
+
+        cpu.execute_opcode();
+
+        // V is also set from $80, the sum after the low nibble is corrected.
+        assert_eq!(cpu.registers.a, 0x80);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert!(cpu.registers.p.overflow_flag);
+
+        // The 65C02 sets N and Z from the corrected result.
+        cpu.variant = CpuVariant::Wdc65C02;
+        cpu.registers.a = 0x99;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8001] = 0x01;
+
+        cpu.execute_opcode();
+
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+    }
+
     #[test]
     fn test_6a_ror_accumulator_instruction_without_carry() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3396,6 +8692,112 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_6b_arr_immediate_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.overflow_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x6B;
+        cpu.memory.contents[0x8001] = 0x80;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0x40);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_6b_arr_immediate_instruction_ignores_bit_0() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x01;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x6B;
+        cpu.memory.contents[0x8001] = 0x01;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0x00);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert!(cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_6b_arr_immediate_instruction_with_carry() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x6B;
+        cpu.memory.contents[0x8001] = 0xFF;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0xFF);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_6b_arr_immediate_instruction_decimal() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xFF;
+        cpu.registers.p.decimal_flag = true;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.overflow_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0x6B;
+        cpu.memory.contents[0x8001] = 0x55;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0x80);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_6c_jmp_absolute_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3407,16 +8809,41 @@ mod tests {
         cpu.memory.contents[0x8001] = 0x00;
         cpu.memory.contents[0x8002] = 0x30;
 
-        let option_return_values = cpu.execute_opcode();
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.pc, 0x4000);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_6c_jmp_indirect_page_boundary_bug() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x30FF] = 0x00;
+        cpu.memory.contents[0x3000] = 0x40;
+        cpu.memory.contents[0x3100] = 0x50;
+        cpu.memory.contents[0x8000] = 0x6C;
+        cpu.memory.contents[0x8001] = 0xFF;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        cpu.execute_opcode().unwrap();
 
-        assert!(option_return_values.is_some());
+        assert_eq!(cpu.registers.pc, 0x4000);
 
-        let return_values = option_return_values.unwrap();
+        // The 65C02 reads across the page boundary.
+        cpu.variant = CpuVariant::Wdc65C02;
+        cpu.registers.pc = 0x8000;
 
-        assert_eq!(cpu.registers.pc, 0x4000);
-        assert_eq!(return_values.bytes, 3);
-        assert_eq!(return_values.clock_periods, 5);
-        assert!(return_values.set_program_counter);
+        cpu.execute_opcode().unwrap();
+
+        assert_eq!(cpu.registers.pc, 0x5000);
     }
 
     #[test]
@@ -3478,6 +8905,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_6f_rra_absolute_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x80;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.overflow_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3000] = 0x02;
+        cpu.memory.contents[0x8000] = 0x6F;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3000], 0x81);
+        assert_eq!(cpu.registers.a, 0x01);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_70_bvs_relative_instruction_with_overflow_not_set() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3516,7 +8972,7 @@ mod tests {
 
         assert_eq!(cpu.registers.pc, 0x8004);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 3);
         assert!(return_values.set_program_counter);
     }
 
@@ -3553,6 +9009,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_73_rra_indirect_y_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x10;
+        cpu.registers.y = 0x10;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0xF0;
+        cpu.memory.contents[0x0031] = 0x40;
+        cpu.memory.contents[0x4100] = 0x01;
+        cpu.memory.contents[0x8000] = 0x73;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4100], 0x00);
+        assert_eq!(cpu.registers.a, 0x11);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_75_adc_zero_page_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3612,6 +9099,33 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_77_rra_zero_page_x_instruction_decimal() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x09;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.decimal_flag = true;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x24;
+        cpu.memory.contents[0x8000] = 0x77;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0032], 0x12);
+        assert_eq!(cpu.registers.a, 0x21);
+        assert!(!cpu.registers.p.carry_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_78_sei_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3664,6 +9178,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_7b_rra_absolute_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.overflow_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3002] = 0x80;
+        cpu.memory.contents[0x8000] = 0x7B;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3002], 0x40);
+        assert_eq!(cpu.registers.a, 0x80);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_7d_adc_absolute_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3725,6 +9269,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_7f_rra_absolute_x_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x80;
+        cpu.registers.x = 0x10;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.overflow_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3100] = 0x02;
+        cpu.memory.contents[0x8000] = 0x7F;
+        cpu.memory.contents[0x8001] = 0xF0;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3100], 0x81);
+        assert_eq!(cpu.registers.a, 0x01);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_81_sta_indirect_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3750,6 +9324,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_83_sax_indirect_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xF3;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x0033] = 0x40;
+        cpu.memory.contents[0x4000] = 0x00;
+        cpu.memory.contents[0x8000] = 0x83;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4000], 0x02);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_84_sty_zero_page_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3816,6 +9419,33 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_87_sax_zero_page_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xF3;
+        cpu.registers.x = 0x3C;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0x00;
+        cpu.memory.contents[0x8000] = 0x87;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0030], 0x30);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 3);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_88_dey_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3935,6 +9565,34 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_8f_sax_absolute_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xF3;
+        cpu.registers.x = 0x3C;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3000] = 0x00;
+        cpu.memory.contents[0x8000] = 0x8F;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3000], 0x30);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 4);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_90_bcc_relative_instruction_with_carry_not_set() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -3952,7 +9610,7 @@ mod tests {
 
         assert_eq!(cpu.registers.pc, 0x8004);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 3);
         assert!(return_values.set_program_counter);
     }
 
@@ -4071,6 +9729,34 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_97_sax_zero_page_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0xF3;
+        cpu.registers.x = 0x3C;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x8000] = 0x97;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0032], 0x30);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 4);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_98_tya_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -4237,11 +9923,41 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert_eq!(cpu.registers.x, 0xff);
+        assert_eq!(cpu.registers.x, 0xff);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_a3_lax_indirect_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x0033] = 0x40;
+        cpu.memory.contents[0x4000] = 0xFF;
+        cpu.memory.contents[0x8000] = 0xA3;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0xFF);
+        assert_eq!(cpu.registers.x, 0xFF);
         assert!(!cpu.registers.p.zero_flag);
         assert!(cpu.registers.p.negative_flag);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 6);
         assert!(!return_values.set_program_counter);
     }
 
@@ -4324,6 +10040,51 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_a7_lax_zero_page_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.x = 0x00;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0xFF;
+        cpu.memory.contents[0x8000] = 0xA7;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0xFF);
+        assert_eq!(cpu.registers.x, 0xFF);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 3);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_a7_lax_zero_page_instruction_isnt_on_the_65c02() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.variant = CpuVariant::Wdc65C02;
+        cpu.registers.a = 0x00;
+        cpu.registers.x = 0x00;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0xFF;
+        cpu.memory.contents[0x8000] = 0xA7;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        assert!(cpu.execute_opcode().is_none());
+        assert_eq!(cpu.registers.a, 0x00);
+        assert_eq!(cpu.registers.x, 0x00);
+    }
+
     #[test]
     fn test_a8_tay_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -4484,6 +10245,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_af_lax_absolute_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.x = 0x00;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3000] = 0xFF;
+        cpu.memory.contents[0x8000] = 0xAF;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0xFF);
+        assert_eq!(cpu.registers.x, 0xFF);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 4);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_b0_bcs_relative_instruction_with_carry_not_set() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -4522,7 +10312,7 @@ mod tests {
 
         assert_eq!(cpu.registers.pc, 0x8004);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 3);
         assert!(return_values.set_program_counter);
     }
 
@@ -4555,6 +10345,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_b3_lax_indirect_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.x = 0x00;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0x00;
+        cpu.memory.contents[0x0031] = 0x40;
+        cpu.memory.contents[0x4002] = 0xFF;
+        cpu.memory.contents[0x8000] = 0xB3;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0xFF);
+        assert_eq!(cpu.registers.x, 0xFF);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_b4_ldy_zero_page_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -4637,10 +10458,40 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_b7_lax_zero_page_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.x = 0x00;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0xFF;
+        cpu.memory.contents[0x8000] = 0xB7;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0xFF);
+        assert_eq!(cpu.registers.x, 0xFF);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 4);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_b8_clv_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
         cpu.registers.p.overflow_flag = true;
+        cpu.registers.p.carry_flag = true;
         cpu.registers.pc = 0x8000;
 
         cpu.memory.contents[0x8000] = 0xb8;
@@ -4651,7 +10502,8 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.is_flag_set(Flag::Overflow));
+        assert!(cpu.is_flag_set(Flag::Carry));
         assert_eq!(return_values.bytes, 1);
         assert_eq!(return_values.clock_periods, 2);
         assert!(!return_values.set_program_counter);
@@ -4796,6 +10648,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_bf_lax_absolute_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.x = 0x00;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3002] = 0xFF;
+        cpu.memory.contents[0x8000] = 0xBF;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0xFF);
+        assert_eq!(cpu.registers.x, 0xFF);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 4);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_c0_cpy_immediate_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -4852,6 +10734,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_c3_dcp_indirect_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x0033] = 0x40;
+        cpu.memory.contents[0x4000] = 0x41;
+        cpu.memory.contents[0x8000] = 0xC3;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4000], 0x40);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_c4_cpy_zero_page_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -4931,6 +10844,34 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_c7_dcp_zero_page_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0x50;
+        cpu.memory.contents[0x8000] = 0xC7;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0030], 0x4F);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_c8_iny_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -4956,16 +10897,65 @@ mod tests {
     }
 
     #[test]
-    fn test_c9_cmp_immediate_instruction() {
+    fn test_c9_cmp_immediate_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x10;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0xc9;
+        cpu.memory.contents[0x8001] = 0x11;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert!(cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.carry_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_ca_dex_implied_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.x = 0x00;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0xCA;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.x, 0xFF);
+        assert!(cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 1);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_cb_sbx_immediate_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.a = 0x10;
-        cpu.registers.p.negative_flag = false;
-        cpu.registers.p.zero_flag = true;
-        cpu.registers.p.carry_flag = true;
+        cpu.registers.a = 0xF0;
+        cpu.registers.x = 0x3C;
+        cpu.registers.p.carry_flag = false;
         cpu.registers.pc = 0x8000;
 
-        cpu.memory.contents[0x8000] = 0xc9;
-        cpu.memory.contents[0x8001] = 0x11;
+        cpu.memory.contents[0x8000] = 0xCB;
+        cpu.memory.contents[0x8001] = 0x10;
 
         let option_return_values = cpu.execute_opcode();
 
@@ -4973,23 +10963,26 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert!(cpu.registers.p.negative_flag);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.negative_flag);
         assert!(!cpu.registers.p.zero_flag);
-        assert!(!cpu.registers.p.carry_flag);
+        assert_eq!(cpu.registers.x, 0x20);
+        assert_eq!(cpu.registers.a, 0xF0);
         assert_eq!(return_values.bytes, 2);
         assert_eq!(return_values.clock_periods, 2);
         assert!(!return_values.set_program_counter);
     }
 
     #[test]
-    fn test_ca_dex_implied_instruction() {
+    fn test_cb_sbx_immediate_instruction_with_borrow() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
-        cpu.registers.x = 0x00;
-        cpu.registers.p.negative_flag = false;
-        cpu.registers.p.zero_flag = true;
+        cpu.registers.a = 0xF0;
+        cpu.registers.x = 0x3C;
+        cpu.registers.p.carry_flag = true;
         cpu.registers.pc = 0x8000;
 
-        cpu.memory.contents[0x8000] = 0xCA;
+        cpu.memory.contents[0x8000] = 0xCB;
+        cpu.memory.contents[0x8001] = 0x40;
 
         let option_return_values = cpu.execute_opcode();
 
@@ -4997,10 +10990,11 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert_eq!(cpu.registers.x, 0xFF);
+        assert!(!cpu.registers.p.carry_flag);
         assert!(cpu.registers.p.negative_flag);
         assert!(!cpu.registers.p.zero_flag);
-        assert_eq!(return_values.bytes, 1);
+        assert_eq!(cpu.registers.x, 0xF0);
+        assert_eq!(return_values.bytes, 2);
         assert_eq!(return_values.clock_periods, 2);
         assert!(!return_values.set_program_counter);
     }
@@ -5087,6 +11081,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_cf_dcp_absolute_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3000] = 0x00;
+        cpu.memory.contents[0x8000] = 0xCF;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3000], 0xFF);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_d0_bne_relative_instruction_with_zero_set() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5125,7 +11148,7 @@ mod tests {
 
         assert_eq!(cpu.registers.pc, 0x8004);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 3);
         assert!(return_values.set_program_counter);
     }
 
@@ -5159,6 +11182,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_d3_dcp_indirect_y_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.y = 0x10;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0xF0;
+        cpu.memory.contents[0x0031] = 0x40;
+        cpu.memory.contents[0x4100] = 0x21;
+        cpu.memory.contents[0x8000] = 0xD3;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4100], 0x20);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_d5_cmp_zero_page_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5213,6 +11267,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_d7_dcp_zero_page_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x41;
+        cpu.memory.contents[0x8000] = 0xD7;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0032], 0x40);
+        assert!(cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_d8_cld_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5227,7 +11310,7 @@ mod tests {
 
         let return_values = option_return_values.unwrap();
 
-        assert!(!cpu.registers.p.decimal_flag);
+        assert!(!cpu.is_flag_set(Flag::Decimal));
         assert_eq!(return_values.bytes, 1);
         assert_eq!(return_values.clock_periods, 2);
         assert!(!return_values.set_program_counter);
@@ -5262,6 +11345,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_db_dcp_absolute_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3002] = 0x50;
+        cpu.memory.contents[0x8000] = 0xDB;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3002], 0x4F);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_dd_cmp_absolute_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5318,6 +11431,36 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_df_dcp_absolute_x_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x40;
+        cpu.registers.x = 0x10;
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.negative_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3100] = 0x21;
+        cpu.memory.contents[0x8000] = 0xDF;
+        cpu.memory.contents[0x8001] = 0xF0;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3100], 0x20);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_e0_cpx_immediate_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5377,6 +11520,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_e3_isc_indirect_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x00;
+        cpu.memory.contents[0x0033] = 0x40;
+        cpu.memory.contents[0x4000] = 0x00;
+        cpu.memory.contents[0x8000] = 0xE3;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4000], 0x01);
+        assert_eq!(cpu.registers.a, 0xFF);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_e4_cpx_zero_page_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5459,6 +11633,34 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_e7_isc_zero_page_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x50;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0x0F;
+        cpu.memory.contents[0x8000] = 0xE7;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0030], 0x10);
+        assert_eq!(cpu.registers.a, 0x40);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 5);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_e8_inx_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5670,20 +11872,105 @@ mod tests {
         cpu.memory.contents[0x8000] = 0xe9;
         cpu.memory.contents[0x8001] = 0x00; // 127d - 0d = 127d, which is not an overflow
 
-        let option_return_values = cpu.execute_opcode();
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.registers.a, 0x7f);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert!(cpu.registers.p.carry_flag); // no borrow
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 2);
+        assert!(!return_values.set_program_counter);
+    }
+
+    #[test]
+    fn test_e9_sbc_immediate_instruction_decimal() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x00;
+        cpu.registers.p.decimal_flag = true;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8000] = 0xE9;
+        cpu.memory.contents[0x8001] = 0x01; // 00 - 01 = 99 with a borrow.
+
+        cpu.execute_opcode();
+
+        // N, V, Z and C come from the binary result, $FF.
+        assert_eq!(cpu.registers.a, 0x99);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+
+        cpu.registers.a = 0x80;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.execute_opcode();
+
+        // 80 - 01 = 79. The binary result, $7F, overflows.
+        assert_eq!(cpu.registers.a, 0x79);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(cpu.registers.p.overflow_flag);
+
+        cpu.registers.a = 0x32;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x8001] = 0x17; // 32 - 17 - 1 = 14.
+
+        cpu.execute_opcode();
+
+        assert_eq!(cpu.registers.a, 0x14);
+        assert!(cpu.registers.p.carry_flag);
+    }
+
+    #[test]
+    fn test_adc_and_sbc_binary_flags_for_every_operand() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+
+        for opcode in [0x69u8, 0xe9] {
+            for a in 0..=0xffu8 {
+                for value in 0..=0xffu8 {
+                    for carry in [false, true] {
+                        cpu.registers.a = a;
+                        cpu.registers.p.carry_flag = carry;
+                        cpu.registers.pc = 0x8000;
+
+                        cpu.memory.contents[0x8000] = opcode;
+                        cpu.memory.contents[0x8001] = value;
 
-        assert!(option_return_values.is_some());
+                        cpu.execute_opcode();
 
-        let return_values = option_return_values.unwrap();
+                        // SBC adds the ones' complement of the operand.
+                        let operand = match opcode {
+                            0x69 => value,
+                            _ => !value,
+                        };
 
-        assert_eq!(cpu.registers.a, 0x7f);
-        assert!(!cpu.registers.p.zero_flag);
-        assert!(!cpu.registers.p.negative_flag);
-        assert!(!cpu.registers.p.overflow_flag);
-        assert!(cpu.registers.p.carry_flag); // no borrow
-        assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
-        assert!(!return_values.set_program_counter);
+                        let sum = a as u16 + operand as u16 + carry as u16;
+                        let result = sum as u8;
+
+                        assert_eq!(cpu.registers.a, result);
+                        assert_eq!(cpu.registers.p.carry_flag, sum > 0xff);
+                        assert_eq!(cpu.registers.p.zero_flag, result == 0);
+                        assert_eq!(cpu.registers.p.negative_flag, result & 0x80 != 0);
+                        assert_eq!(
+                            cpu.registers.p.overflow_flag,
+                            (a as i8 as i16 + operand as i8 as i16 + carry as i16) != result as i8 as i16
+                        );
+                    }
+                }
+            }
+        }
     }
 
     #[test]
@@ -5705,6 +11992,63 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_undocumented_nops() {
+        let nops: [(u8, u8, u8); 27] = [
+            (0x04, 2, 3),
+            (0x0C, 3, 4),
+            (0x14, 2, 4),
+            (0x1A, 1, 2),
+            (0x1C, 3, 4),
+            (0x34, 2, 4),
+            (0x3A, 1, 2),
+            (0x3C, 3, 4),
+            (0x44, 2, 3),
+            (0x54, 2, 4),
+            (0x5A, 1, 2),
+            (0x5C, 3, 4),
+            (0x64, 2, 3),
+            (0x74, 2, 4),
+            (0x7A, 1, 2),
+            (0x7C, 3, 4),
+            (0x80, 2, 2),
+            (0x82, 2, 2),
+            (0x89, 2, 2),
+            (0xC2, 2, 2),
+            (0xD4, 2, 4),
+            (0xDA, 1, 2),
+            (0xDC, 3, 4),
+            (0xE2, 2, 2),
+            (0xF4, 2, 4),
+            (0xFA, 1, 2),
+            (0xFC, 3, 4),
+        ];
+
+        for (opcode, bytes, cycles) in nops {
+            let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+            cpu.registers.a = 0x11;
+            cpu.registers.x = 0x01;
+            cpu.registers.pc = 0x8000;
+            cpu.memory
+                .save_u8_vector_into_memory(0x8000, vec![opcode, 0xF0, 0x30]);
+
+            let return_values = cpu.execute_opcode().unwrap();
+
+            assert_eq!(return_values.bytes, bytes, "${:02X}", opcode);
+            assert_eq!(return_values.clock_periods, cycles, "${:02X}", opcode);
+            assert_eq!(cpu.registers.a, 0x11);
+
+            // The absolute,X NOPs take an extra cycle when crossing a page.
+            if opcode & 0x1F == 0x1C {
+                cpu.registers.x = 0x10;
+
+                let return_values = cpu.execute_opcode().unwrap();
+
+                assert_eq!(return_values.clock_periods, 5, "${:02X}", opcode);
+            }
+        }
+    }
+
     #[test]
     fn test_ec_cpx_absolute_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5790,6 +12134,35 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_ef_isc_absolute_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x50;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.overflow_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3000] = 0xAF;
+        cpu.memory.contents[0x8000] = 0xEF;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3000], 0xB0);
+        assert_eq!(cpu.registers.a, 0xA0);
+        assert!(!cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert!(cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_f0_beq_relative_instruction_with_zero_not_set() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5828,10 +12201,83 @@ mod tests {
 
         assert_eq!(cpu.registers.pc, 0x8004);
         assert_eq!(return_values.bytes, 2);
-        assert_eq!(return_values.clock_periods, 2);
+        assert_eq!(return_values.clock_periods, 3);
         assert!(return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_f0_beq_relative_instruction_cycle_counts() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+
+        // Not taken.
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.pc = 0x80F0;
+
+        cpu.memory.contents[0x80F0] = 0xF0;
+        cpu.memory.contents[0x80F1] = 0x04;
+
+        let return_values = cpu.execute_opcode().unwrap();
+
+        assert_eq!(cpu.registers.pc, 0x80F2);
+        assert_eq!(return_values.clock_periods, 2);
+
+        // Taken on the same page.
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.pc = 0x80F0;
+
+        let return_values = cpu.execute_opcode().unwrap();
+
+        assert_eq!(cpu.registers.pc, 0x80F6);
+        assert_eq!(return_values.clock_periods, 3);
+
+        // Taken across a page boundary.
+        cpu.registers.pc = 0x80F0;
+
+        cpu.memory.contents[0x80F1] = 0x10;
+
+        let return_values = cpu.execute_opcode().unwrap();
+
+        assert_eq!(cpu.registers.pc, 0x8102);
+        assert_eq!(return_values.clock_periods, 4);
+    }
+
+    #[test]
+    fn test_f0_beq_relative_instruction_at_top_of_memory() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+
+        // Not taken.
+        cpu.registers.p.zero_flag = false;
+        cpu.registers.pc = 0xFFFE;
+
+        cpu.memory.contents[0xFFFE] = 0xF0;
+        cpu.memory.contents[0xFFFF] = 0x02;
+
+        let return_values = cpu.execute_opcode().unwrap();
+
+        assert_eq!(cpu.registers.pc, 0x0000);
+        assert_eq!(return_values.clock_periods, 2);
+
+        // Taken to the bottom of memory.
+        cpu.registers.p.zero_flag = true;
+        cpu.registers.pc = 0xFFFE;
+
+        let return_values = cpu.execute_opcode().unwrap();
+
+        assert_eq!(cpu.registers.pc, 0x0002);
+        assert_eq!(return_values.clock_periods, 3);
+
+        // The operand of a branch at $FFFF is read from $0000.
+        cpu.registers.pc = 0xFFFF;
+
+        cpu.memory.contents[0xFFFF] = 0xF0;
+        cpu.memory.contents[0x0000] = 0xFC;
+
+        let return_values = cpu.execute_opcode().unwrap();
+
+        assert_eq!(cpu.registers.pc, 0xFFFD);
+        assert_eq!(return_values.clock_periods, 4);
+    }
+
     #[test]
     fn test_f1_sbc_indirect_y_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5865,6 +12311,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_f3_isc_indirect_y_instruction_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x80;
+        cpu.registers.y = 0x10;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.overflow_flag = false;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0030] = 0xF0;
+        cpu.memory.contents[0x0031] = 0x40;
+        cpu.memory.contents[0x4100] = 0x0F;
+        cpu.memory.contents[0x8000] = 0xF3;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x4100], 0x10);
+        assert_eq!(cpu.registers.a, 0x70);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 8);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_f5_sbc_zero_page_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5922,6 +12399,34 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_f7_isc_zero_page_x_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x50;
+        cpu.registers.x = 0x02;
+        cpu.registers.p.carry_flag = false;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x0032] = 0x0F;
+        cpu.memory.contents[0x8000] = 0xF7;
+        cpu.memory.contents[0x8001] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x0032], 0x10);
+        assert_eq!(cpu.registers.a, 0x3F);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert_eq!(return_values.bytes, 2);
+        assert_eq!(return_values.clock_periods, 6);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_f8_sed_implied_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -5974,6 +12479,37 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_fb_isc_absolute_y_instruction() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x50;
+        cpu.registers.y = 0x02;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.p.overflow_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3002] = 0xFF;
+        cpu.memory.contents[0x8000] = 0xFB;
+        cpu.memory.contents[0x8001] = 0x00;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3002], 0x00);
+        assert_eq!(cpu.registers.a, 0x50);
+        assert!(cpu.registers.p.carry_flag);
+        assert!(!cpu.registers.p.overflow_flag);
+        assert!(!cpu.registers.p.negative_flag);
+        assert!(!cpu.registers.p.zero_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_fd_sbc_absolute_x_instruction() {
         let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
@@ -6033,6 +12569,34 @@ mod tests {
         assert!(!return_values.set_program_counter);
     }
 
+    #[test]
+    fn test_ff_isc_absolute_x_instruction_decimal_crossing_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.a = 0x50;
+        cpu.registers.x = 0x10;
+        cpu.registers.p.decimal_flag = true;
+        cpu.registers.p.carry_flag = true;
+        cpu.registers.pc = 0x8000;
+
+        cpu.memory.contents[0x3100] = 0x08;
+        cpu.memory.contents[0x8000] = 0xFF;
+        cpu.memory.contents[0x8001] = 0xF0;
+        cpu.memory.contents[0x8002] = 0x30;
+
+        let option_return_values = cpu.execute_opcode();
+
+        assert!(option_return_values.is_some());
+
+        let return_values = option_return_values.unwrap();
+
+        assert_eq!(cpu.memory.contents[0x3100], 0x09);
+        assert_eq!(cpu.registers.a, 0x41);
+        assert!(cpu.registers.p.carry_flag);
+        assert_eq!(return_values.bytes, 3);
+        assert_eq!(return_values.clock_periods, 7);
+        assert!(!return_values.set_program_counter);
+    }
+
     #[test]
     fn test_nmi_interrupt() {
         let mut cpu: Cpu = Cpu::new(0x8008, 1_000_000.0);
@@ -6076,6 +12640,146 @@ mod tests {
         assert_eq!(cpu.memory.contents[0x01FF], 0x80);
     }
 
+    #[test]
+    fn test_irq_is_serviced_right_after_rti_clears_interrupt_disable() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        cpu.memory.set_16_bit_value(IRQ_BRK_VECTOR, 0x4000);
+        cpu.memory.contents[0x8000] = 0x40;
+
+        // RTI returns to $9000 with the interrupt disable flag clear.
+        cpu.push_u16(0x9000);
+        cpu.push_u8(0x20);
+
+        cpu.irq_triggered = true;
+
+        cpu.poll_interrupts();
+
+        assert_eq!(cpu.registers.pc, 0x8000);
+
+        cpu.execute_instruction();
+
+        assert!(!cpu.registers.p.interrupt_disable_flag);
+        assert_eq!(cpu.registers.pc, 0x9000);
+
+        cpu.poll_interrupts();
+
+        assert_eq!(cpu.registers.pc, 0x4000);
+        assert!(!cpu.irq_triggered);
+    }
+
+    #[test]
+    fn test_push_u16_wraps_within_the_stack_page() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.registers.sp = 0x00;
+
+        cpu.push_u16(0x1234);
+
+        assert_eq!(cpu.memory.contents[0x0100], 0x12);
+        assert_eq!(cpu.memory.contents[0x01FF], 0x34);
+        assert_eq!(cpu.memory.contents[0x00FF], 0x00);
+        assert_eq!(cpu.registers.sp, 0xFE);
+    }
+
+    #[test]
+    fn test_interrupt_bus_order() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+        cpu.registers.p.from_byte(0x21);
+        cpu.registers.pc = 0x1234;
+
+        let bus = Rc::new(RefCell::new(Vec::new()));
+
+        let writes = bus.clone();
+        cpu.memory.add_write_hook(0x0100, 0x01FF, move |address, value| {
+            writes.borrow_mut().push(('W', address, value));
+        });
+
+        let reads = bus.clone();
+        cpu.memory.add_read_hook(0xFFFA, 0xFFFB, move |address| {
+            reads.borrow_mut().push(('R', address, 0x00));
+            match address {
+                0xFFFA => 0x00,
+                _ => 0x30,
+            }
+        });
+
+        cpu.nmi_triggered = true;
+
+        cpu.poll_interrupts();
+
+        assert_eq!(
+            *bus.borrow(),
+            vec![
+                ('W', 0x01FF, 0x12),
+                ('W', 0x01FE, 0x34),
+                ('W', 0x01FD, 0x21),
+                ('R', 0xFFFA, 0x00),
+                ('R', 0xFFFB, 0x00),
+            ]
+        );
+        assert_eq!(cpu.registers.pc, 0x3000);
+        assert_eq!(cpu.registers.sp, 0xFC);
+    }
+
+    #[test]
+    fn test_nmi_takes_priority_over_irq() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+        cpu.registers.p.interrupt_disable_flag = false;
+
+        cpu.memory.set_16_bit_value(NMI_VECTOR, 0x3000);
+        cpu.memory.set_16_bit_value(IRQ_BRK_VECTOR, 0x4000);
+
+        // RTI
+        cpu.memory.contents[0x3000] = 0x40;
+
+        cpu.nmi_triggered = true;
+        cpu.irq_triggered = true;
+
+        cpu.poll_interrupts();
+
+        assert_eq!(cpu.registers.pc, 0x3000);
+        assert!(!cpu.nmi_triggered);
+        assert!(cpu.irq_triggered);
+
+        cpu.execute_instruction();
+
+        assert_eq!(cpu.registers.pc, 0x8000);
+
+        cpu.poll_interrupts();
+
+        assert_eq!(cpu.registers.pc, 0x4000);
+        assert!(!cpu.irq_triggered);
+    }
+
+    #[test]
+    fn test_irq_is_delayed_one_instruction_after_cli() {
+        let mut cpu: Cpu = Cpu::new(0x8000, 1_000_000.0);
+        cpu.power_up();
+
+        cpu.memory.set_16_bit_value(IRQ_BRK_VECTOR, 0x4000);
+
+        // CLI, NOP
+        cpu.memory.save_u8_vector_into_memory(0x8000, vec![0x58, 0xEA]);
+
+        cpu.irq_triggered = true;
+
+        cpu.execute_instruction();
+        cpu.poll_interrupts();
+
+        assert_eq!(cpu.registers.pc, 0x8001);
+        assert!(cpu.irq_triggered);
+
+        cpu.execute_instruction();
+        cpu.poll_interrupts();
+
+        assert_eq!(cpu.registers.pc, 0x4000);
+        assert!(!cpu.irq_triggered);
+        assert_eq!(cpu.memory.get_16_bit_value(0x01FE), 0x8002);
+    }
+
     #[test]
     fn test_irq_interrupt_interrupts_disabled() {
         let mut cpu: Cpu = Cpu::new(0x8008, 1_000_000.0);
@@ -0,0 +1,44 @@
+use super::Cpu;
+
+/// The kind of memory access that triggers a watchpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatchKind {
+    Read,
+    Write,
+}
+
+/// The kind of a breakpoint returned by `Cpu::list_breakpoints()` and `Cpu::list_watchpoints()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakpointKind {
+    /// Stops when the program counter reaches the address.
+    Address,
+    /// Stops when the condition holds, optionally only at an address.
+    Conditional,
+    /// Stops before an instruction accesses the address.
+    Watchpoint(WatchKind),
+}
+
+/// A uniform description of a breakpoint or watchpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BreakpointInfo {
+    /// The address of the breakpoint. `None` for conditional breakpoints checked at every address.
+    pub address: Option<u16>,
+    pub kind: BreakpointKind,
+    /// The number of times the breakpoint has stopped execution.
+    pub hit_count: u64,
+}
+
+/// A breakpoint that stops execution when its condition returns `true`.
+pub struct ConditionalBreakpoint {
+    /// Only check the condition when the program counter is at this address. `None` checks it before every instruction.
+    pub address: Option<u16>,
+    pub condition: Box<dyn Fn(&Cpu) -> bool>,
+    pub hit_count: u64,
+}
+
+/// A watchpoint that stops execution before an instruction accesses its address.
+pub struct Watchpoint {
+    pub address: u16,
+    pub kind: WatchKind,
+    pub hit_count: u64,
+}
@@ -0,0 +1,70 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// The source of time `Cpu` uses to throttle execution to its clock speed.
+pub trait Clock {
+    /// Returns the time elapsed since an arbitrary, fixed starting point.
+    fn now(&self) -> Duration;
+
+    /// Waits for `duration` to pass.
+    fn sleep(&mut self, duration: Duration);
+}
+
+/// The real time, used by default.
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// A virtual time that only advances when the cpu sleeps, so throttling can be tested
+/// deterministically without waiting. Clones share the same time, so a test can keep a
+/// clone to inspect after passing one to `Cpu::set_clock()`.
+#[derive(Clone, Default)]
+pub struct MockClock {
+    elapsed: Rc<Cell<Duration>>,
+}
+
+impl MockClock {
+    pub fn new() -> MockClock {
+        MockClock::default()
+    }
+
+    /// Returns the total time the cpu has slept.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed.get()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        self.elapsed.get()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        self.elapsed.set(self.elapsed.get() + duration);
+    }
+}
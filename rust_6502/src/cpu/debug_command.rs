@@ -0,0 +1,248 @@
+use std::fmt::Display;
+
+const DEFAULT_DISPLAY_LENGTH: u16 = 16;
+
+/// A command entered at the debugger prompt of `Cpu::run()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugCommand {
+    /// `B $XXXX` toggles a breakpoint at the address.
+    ToggleBreakpoint(u16),
+    /// `D $XXXX [length]` displays `length` bytes of memory, 16 by default.
+    DisplayMemory { address: u16, length: u16 },
+    /// `K` displays the stack from the top of stack to $01FF.
+    DisplayStack,
+    /// `S [count]` executes `count` instructions, 1 by default.
+    Step(u16),
+    /// `T` toggles trapping.
+    ToggleTrapping,
+    /// `X` resumes execution.
+    Execute,
+    /// `V` toggles the registers and disassembly shown before each prompt.
+    ToggleVerbose,
+    /// `P` pauses `Cpu::run()` so it can be resumed.
+    Pause,
+    /// `Q` quits `Cpu::run()`.
+    Quit,
+    /// `H` lists the commands entered so far.
+    History,
+    /// `?` or an empty line shows the help.
+    Help,
+}
+
+/// The reasons a debugger command can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebugParseError {
+    /// The command isn't one of the debugger's commands.
+    UnrecognizedCommand(String),
+    /// The command requires a hexadecimal address such as `$FFE2`.
+    InvalidAddress(DebugCommandKind),
+    /// The display memory length isn't a decimal or `$` hexadecimal number.
+    InvalidLength(String),
+    /// The step count isn't a decimal or `$` hexadecimal number.
+    InvalidStepCount(String),
+}
+
+/// The commands that take an address, used by `DebugParseError::InvalidAddress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DebugCommandKind {
+    ToggleBreakpoint,
+    DisplayMemory,
+}
+
+impl Display for DebugParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DebugParseError::UnrecognizedCommand(_) => write!(f, "Unrecognized command"),
+            DebugParseError::InvalidAddress(DebugCommandKind::ToggleBreakpoint) => write!(
+                f,
+                "Breakpoint requires an valid hexadecimal address: B $FFE2."
+            ),
+            DebugParseError::InvalidAddress(DebugCommandKind::DisplayMemory) => write!(
+                f,
+                "Display memory requires an valid hexadecimal address: D $FFE2."
+            ),
+            DebugParseError::InvalidLength(length) => write!(
+                f,
+                "Display memory requires a valid length, not {}: D $FFE2 32.",
+                length
+            ),
+            DebugParseError::InvalidStepCount(count) => {
+                write!(f, "Step requires a valid count, not {}: S 10.", count)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DebugParseError {}
+
+/// Parses a line entered at the debugger prompt. Commands aren't case sensitive.
+///
+/// # Arguments
+/// * `input` - The line to parse.
+///
+/// # Examples
+/// ```
+/// use rust_6502::cpu::debug_command::{parse_debug_command, DebugCommand};
+///
+/// assert_eq!(
+///     parse_debug_command("b $c000"),
+///     Ok(DebugCommand::ToggleBreakpoint(0xC000))
+/// );
+/// ```
+pub fn parse_debug_command(input: &str) -> Result<DebugCommand, DebugParseError> {
+    let input = input.trim().to_uppercase();
+
+    let split_input: Vec<&str> = input.split_whitespace().collect();
+
+    match split_input.first().copied().unwrap_or("") {
+        "B" => match split_input.get(1).and_then(|address| parse_address(address)) {
+            Some(address) => Ok(DebugCommand::ToggleBreakpoint(address)),
+            None => Err(DebugParseError::InvalidAddress(
+                DebugCommandKind::ToggleBreakpoint,
+            )),
+        },
+        "D" => {
+            let Some(address) = split_input.get(1).and_then(|address| parse_address(address))
+            else {
+                return Err(DebugParseError::InvalidAddress(DebugCommandKind::DisplayMemory));
+            };
+
+            let length = match split_input.get(2) {
+                Some(length) => parse_length(length)
+                    .ok_or_else(|| DebugParseError::InvalidLength(length.to_string()))?,
+                None => DEFAULT_DISPLAY_LENGTH,
+            };
+
+            Ok(DebugCommand::DisplayMemory { address, length })
+        }
+        "K" => Ok(DebugCommand::DisplayStack),
+        "S" => match split_input.get(1) {
+            Some(count) => parse_length(count)
+                .map(DebugCommand::Step)
+                .ok_or_else(|| DebugParseError::InvalidStepCount(count.to_string())),
+            None => Ok(DebugCommand::Step(1)),
+        },
+        "T" => Ok(DebugCommand::ToggleTrapping),
+        "X" => Ok(DebugCommand::Execute),
+        "V" => Ok(DebugCommand::ToggleVerbose),
+        "P" => Ok(DebugCommand::Pause),
+        "Q" => Ok(DebugCommand::Quit),
+        "H" => Ok(DebugCommand::History),
+        "?" | "" => Ok(DebugCommand::Help),
+        command => Err(DebugParseError::UnrecognizedCommand(command.to_string())),
+    }
+}
+
+// An address is a $ followed by 1 to 4 hexadecimal digits.
+fn parse_address(address: &str) -> Option<u16> {
+    let hexadecimal = address.strip_prefix('$')?;
+
+    if !(1..=4).contains(&hexadecimal.len())
+        || !hexadecimal.bytes().all(|byte| byte.is_ascii_hexdigit())
+    {
+        return None;
+    }
+
+    u16::from_str_radix(hexadecimal, 16).ok()
+}
+
+fn parse_length(length: &str) -> Option<u16> {
+    let length = match length.strip_prefix('$') {
+        Some(hexadecimal) => u16::from_str_radix(hexadecimal, 16).ok()?,
+        None => length.parse().ok()?,
+    };
+
+    (length > 0).then_some(length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_toggle_breakpoint() {
+        assert_eq!(
+            parse_debug_command("B $C000"),
+            Ok(DebugCommand::ToggleBreakpoint(0xC000))
+        );
+    }
+
+    #[test]
+    fn test_parse_display_memory() {
+        assert_eq!(
+            parse_debug_command("D $00 32"),
+            Ok(DebugCommand::DisplayMemory {
+                address: 0x0000,
+                length: 32
+            })
+        );
+
+        assert_eq!(
+            parse_debug_command("d $ffe2"),
+            Ok(DebugCommand::DisplayMemory {
+                address: 0xFFE2,
+                length: 16
+            })
+        );
+
+        assert_eq!(
+            parse_debug_command("D $00 $20"),
+            Ok(DebugCommand::DisplayMemory {
+                address: 0x0000,
+                length: 32
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_commands() {
+        assert_eq!(
+            parse_debug_command("Z"),
+            Err(DebugParseError::UnrecognizedCommand("Z".to_string()))
+        );
+
+        assert_eq!(
+            parse_debug_command("B"),
+            Err(DebugParseError::InvalidAddress(
+                DebugCommandKind::ToggleBreakpoint
+            ))
+        );
+
+        for address in ["C000", "$", "$12345", "$+12", "$G000"] {
+            assert_eq!(
+                parse_debug_command(&format!("D {address}")),
+                Err(DebugParseError::InvalidAddress(DebugCommandKind::DisplayMemory))
+            );
+        }
+
+        assert_eq!(
+            parse_debug_command("D $C000 many"),
+            Err(DebugParseError::InvalidLength("MANY".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_step() {
+        assert_eq!(parse_debug_command("S 10"), Ok(DebugCommand::Step(10)));
+        assert_eq!(parse_debug_command("s $10"), Ok(DebugCommand::Step(16)));
+
+        assert_eq!(
+            parse_debug_command("S 0"),
+            Err(DebugParseError::InvalidStepCount("0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_single_letter_commands() {
+        assert_eq!(parse_debug_command("k"), Ok(DebugCommand::DisplayStack));
+        assert_eq!(parse_debug_command("s"), Ok(DebugCommand::Step(1)));
+        assert_eq!(parse_debug_command("T"), Ok(DebugCommand::ToggleTrapping));
+        assert_eq!(parse_debug_command("X"), Ok(DebugCommand::Execute));
+        assert_eq!(parse_debug_command("v"), Ok(DebugCommand::ToggleVerbose));
+        assert_eq!(parse_debug_command("P"), Ok(DebugCommand::Pause));
+        assert_eq!(parse_debug_command("Q"), Ok(DebugCommand::Quit));
+        assert_eq!(parse_debug_command("h"), Ok(DebugCommand::History));
+        assert_eq!(parse_debug_command("?"), Ok(DebugCommand::Help));
+        assert_eq!(parse_debug_command(""), Ok(DebugCommand::Help));
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt::Display;
+
+use super::instruction::{AddressingMode, Instruction};
+use super::Cpu;
+
+/// An instruction decoded from memory by `Cpu::decode_instruction()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedInstruction {
+    pub address: u16,
+    pub opcode: u8,
+    pub mnemonic: &'static str,
+    /// `false` for the undocumented (illegal) opcodes, which the disassembly marks with a `*`.
+    pub is_documented: bool,
+    /// The opcode followed by its operand bytes.
+    pub bytes: Vec<u8>,
+    /// The operand as shown in the disassembly. Relative branches are resolved to their target address.
+    pub operand: String,
+    /// The raw signed offset of a relative branch. `None` for every other addressing mode.
+    pub relative_offset: Option<i8>,
+}
+
+impl DecodedInstruction {
+    /// Decodes an instruction from its bytes, which must hold the opcode and the whole operand.
+    pub(crate) fn new(instruction: &Instruction, address: u16, bytes: Vec<u8>) -> DecodedInstruction {
+        let zero_page = || bytes[1];
+        let absolute = || (bytes[2] as u16) << 8 | bytes[1] as u16;
+
+        let mut relative_offset = None;
+
+        let operand = match instruction.addressing_mode {
+            AddressingMode::Accumulator => "A".to_string(),
+            AddressingMode::Implied => String::new(),
+            AddressingMode::Relative => {
+                relative_offset = Some(zero_page() as i8);
+                let relative_address = Cpu::calculate_address_from_relative_offset(
+                    address.wrapping_add(2),
+                    zero_page(),
+                );
+                format!("${:04X}", relative_address)
+            }
+            AddressingMode::ZeroPage => format!("${:02X}", zero_page()),
+            AddressingMode::Immediate => format!("#${:02X}", zero_page()),
+            AddressingMode::ZeroPageX => format!("${:02X},X", zero_page()),
+            AddressingMode::ZeroPageY => format!("${:02X},Y", zero_page()),
+            AddressingMode::Absolute => format!("${:04X}", absolute()),
+            AddressingMode::AbsoluteX => format!("${:04X},X", absolute()),
+            AddressingMode::AbsoluteY => format!("${:04X},Y", absolute()),
+            AddressingMode::Indirect => format!("(${:04X})", absolute()),
+            AddressingMode::IndirectX => format!("(${:02X},X)", zero_page()),
+            AddressingMode::IndirectY => format!("(${:02X}),Y", zero_page()),
+        };
+
+        DecodedInstruction {
+            address,
+            opcode: instruction.opcode,
+            mnemonic: instruction.mnemonic,
+            is_documented: instruction.is_documented,
+            bytes,
+            operand,
+            relative_offset,
+        }
+    }
+
+    /// Returns the operand followed by the signed offset for relative branches, e.g. `$8010 (-6)`.
+    pub fn operand_with_offset(&self) -> String {
+        match self.relative_offset {
+            Some(offset) => format!("{} ({})", self.operand, offset),
+            None => self.operand.clone(),
+        }
+    }
+}
+
+/// Shows the instruction as `Cpu::disassemble_opcode()` does. Like nestest's log, undocumented
+/// opcodes have a `*` before the mnemonic.
+impl Display for DecodedInstruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut bytes = String::new();
+
+        for byte in &self.bytes {
+            bytes = format!("{} {:02X}", bytes, byte);
+        }
+
+        let prefix = match self.is_documented {
+            true => ' ',
+            false => '*',
+        };
+
+        write!(
+            f,
+            "{:04X} {:<9}{}{:<4} {}",
+            self.address, bytes, prefix, self.mnemonic, self.operand
+        )
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt::Display;
+
+/// The errors that stop the cpu from executing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuError {
+    /// The opcode at the address isn't a recognized instruction.
+    UnrecognizedOpcode { opcode: u8, address: u16 },
+    /// `Cpu::step_out()` was called at the address while the stack was too empty to hold a
+    /// return address, so no `RTS` or `RTI` could return.
+    NoReturnAddress { address: u16 },
+    /// `Cpu::step_over()` was stepping a subroutine that trapped at the address, such as a
+    /// `JMP *` idle loop, so it would never return.
+    Trapped { address: u16 },
+}
+
+impl Display for CpuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CpuError::UnrecognizedOpcode { opcode, address } => {
+                write!(f, "Unrecognized opcode: {:02X} @ {:04X}", opcode, address)
+            }
+            CpuError::NoReturnAddress { address } => {
+                write!(f, "No return address on the stack @ {:04X}", address)
+            }
+            CpuError::Trapped { address } => {
+                write!(f, "Trapped before returning @ {:04X}", address)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CpuError {}
@@ -1,6 +1,7 @@
+use super::variant::CpuVariant;
 use super::Cpu;
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum AddressingMode {
     Accumulator,
     Implied,
@@ -22,6 +23,8 @@ pub struct ExecutionReturnValues {
     pub bytes: u8,
     pub clock_periods: u8,
     pub set_program_counter: bool,
+    /// Set when the instruction's effective address is covered by a memory hook.
+    pub accessed_io: bool,
 }
 
 impl ExecutionReturnValues {
@@ -33,6 +36,7 @@ impl ExecutionReturnValues {
                 false => instruction.clock_periods,
             },
             set_program_counter: instruction.sets_program_counter,
+            accessed_io: false,
         }
     }
 }
@@ -45,10 +49,117 @@ pub struct Instruction {
     pub clock_periods: u8,
     pub addressing_mode: AddressingMode,
     pub sets_program_counter: bool,
+    /// `false` for the undocumented (illegal) opcodes.
+    pub is_documented: bool,
     pub execute: fn(&mut Cpu, Instruction) -> ExecutionReturnValues,
 }
 
 impl Instruction {
+    /// Returns `true` if the instruction reads the memory location its operand addresses.
+    pub fn reads_memory(&self) -> bool {
+        self.accesses_memory()
+            && !matches!(
+                self.mnemonic,
+                JMP_INSTRUCTION
+                    | JSR_INSTRUCTION
+                    | SAX_INSTRUCTION
+                    | STA_INSTRUCTION
+                    | STX_INSTRUCTION
+                    | STY_INSTRUCTION
+            )
+    }
+
+    /// Returns `true` if the instruction writes the memory location its operand addresses.
+    pub fn writes_memory(&self) -> bool {
+        self.accesses_memory()
+            && matches!(
+                self.mnemonic,
+                ASL_INSTRUCTION
+                    | DCP_INSTRUCTION
+                    | DEC_INSTRUCTION
+                    | INC_INSTRUCTION
+                    | ISC_INSTRUCTION
+                    | LSR_INSTRUCTION
+                    | RLA_INSTRUCTION
+                    | ROL_INSTRUCTION
+                    | ROR_INSTRUCTION
+                    | RRA_INSTRUCTION
+                    | SAX_INSTRUCTION
+                    | SLO_INSTRUCTION
+                    | SRE_INSTRUCTION
+                    | STA_INSTRUCTION
+                    | STX_INSTRUCTION
+                    | STY_INSTRUCTION
+            )
+    }
+
+    fn accesses_memory(&self) -> bool {
+        !matches!(
+            self.addressing_mode,
+            AddressingMode::Accumulator
+                | AddressingMode::Implied
+                | AddressingMode::Relative
+                | AddressingMode::Immediate
+                | AddressingMode::Indirect
+        )
+    }
+
+    /// Finds the instruction for an opcode on a specific cpu variant. The WDC 65C02 doesn't
+    /// have the NMOS undocumented instructions, so they aren't found for it.
+    pub fn lookup(opcode: u8, variant: CpuVariant) -> Option<Instruction> {
+        if variant == CpuVariant::Wdc65C02 {
+            if let Some(instruction) = WDC_65C02_INSTRUCTION_SET
+                .iter()
+                .find(|instruction| instruction.opcode == opcode)
+            {
+                return Some(*instruction);
+            }
+
+            return Instruction::binary_search(opcode).filter(|instruction| instruction.is_documented);
+        }
+
+        Instruction::binary_search(opcode)
+    }
+
+    /// Encodes an NMOS 6502 instruction from its mnemonic, addressing mode and operand bytes,
+    /// which are given least significant byte first as returned by `parse_operand()`.
+    ///
+    /// Returns `None` if the mnemonic doesn't support the addressing mode or the operand has
+    /// the wrong number of bytes. When several opcodes encode the same instruction, the
+    /// documented one is preferred.
+    ///
+    /// # Arguments
+    /// * `mnemonic` - The instruction's mnemonic, such as `LDA`.
+    /// * `mode` - The addressing mode.
+    /// * `operand` - The operand bytes.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::instruction::{AddressingMode, Instruction};
+    ///
+    /// assert_eq!(
+    ///     Instruction::encode("STA", AddressingMode::Absolute, &[0x00, 0x20]),
+    ///     Some(vec![0x8D, 0x00, 0x20])
+    /// );
+    /// ```
+    pub fn encode(mnemonic: &str, mode: AddressingMode, operand: &[u8]) -> Option<Vec<u8>> {
+        let mnemonic = mnemonic.trim().to_uppercase();
+
+        let instruction = INSTRUCTION_SET
+            .iter()
+            .filter(|instruction| instruction.mnemonic == mnemonic && instruction.addressing_mode == mode)
+            .min_by_key(|instruction| !instruction.is_documented)?;
+
+        if operand.len() + 1 != instruction.bytes as usize {
+            return None;
+        }
+
+        let mut bytes = vec![instruction.opcode];
+        bytes.extend_from_slice(operand);
+
+        Some(bytes)
+    }
+
     pub fn binary_search(opcode: u8) -> Option<Instruction> {
         let mut low = 0;
         let mut high = INSTRUCTION_SET.len() - 1;
@@ -76,8 +187,127 @@ impl Instruction {
     }
 }
 
+/// The twelve NMOS opcodes that jam (KIL) the cpu until it's reset.
+pub const JAM_OPCODES: [u8; 12] = [
+    0x02, 0x12, 0x22, 0x32, 0x42, 0x52, 0x62, 0x72, 0x92, 0xB2, 0xD2, 0xF2,
+];
+
+/// Returns `true` if the opcode jams an NMOS 6502.
+pub fn is_jam_opcode(opcode: u8) -> bool {
+    JAM_OPCODES.contains(&opcode)
+}
+
+/// Classifies an assembler operand such as `#$12`, `$12,X` or `($1234)` into the addressing
+/// mode the mnemonic uses for it and the operand bytes, least significant byte first.
+///
+/// A one or two digit address uses the zero page form when the mnemonic has one. A branch's
+/// operand is its target address, which is returned as two bytes with `AddressingMode::Relative`
+/// since the offset depends on where the branch is assembled. Returns `None` if the operand
+/// can't be parsed or the mnemonic doesn't support its addressing mode.
+///
+/// # Arguments
+/// * `mnemonic` - The instruction's mnemonic, such as `LDA`.
+/// * `operand` - The operand, using `$` for hexadecimal numbers.
+///
+/// # Examples
+/// ```
+/// use rust_6502::cpu::instruction::{parse_operand, AddressingMode};
+///
+/// assert_eq!(
+///     parse_operand("LDA", "($12),Y"),
+///     Some((AddressingMode::IndirectY, vec![0x12]))
+/// );
+/// ```
+pub fn parse_operand(mnemonic: &str, operand: &str) -> Option<(AddressingMode, Vec<u8>)> {
+    let mnemonic = mnemonic.trim().to_uppercase();
+    let operand: String = operand
+        .to_uppercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+
+    let supports = |mode: AddressingMode| {
+        INSTRUCTION_SET
+            .iter()
+            .chain(WDC_65C02_INSTRUCTION_SET.iter())
+            .any(|instruction| instruction.mnemonic == mnemonic && instruction.addressing_mode == mode)
+    };
+
+    let (mode, value, digits) = if operand.is_empty() {
+        match supports(AddressingMode::Implied) {
+            true => (AddressingMode::Implied, 0, 0),
+            false => (AddressingMode::Accumulator, 0, 0),
+        }
+    } else if operand == "A" {
+        (AddressingMode::Accumulator, 0, 0)
+    } else if let Some(value) = operand.strip_prefix('#') {
+        let (value, _) = parse_hexadecimal(value).filter(|(value, _)| *value <= 0xFF)?;
+
+        (AddressingMode::Immediate, value, 1)
+    } else if let Some(value) = operand.strip_prefix('(') {
+        if let Some(value) = value.strip_suffix(",X)") {
+            (AddressingMode::IndirectX, parse_zero_page(value)?, 1)
+        } else if let Some(value) = value.strip_suffix("),Y") {
+            (AddressingMode::IndirectY, parse_zero_page(value)?, 1)
+        } else {
+            let (value, _) = parse_hexadecimal(value.strip_suffix(')')?)?;
+
+            (AddressingMode::Indirect, value, 2)
+        }
+    } else {
+        let (address, index) = match operand.split_once(',') {
+            Some((address, index)) => (address, Some(index)),
+            None => (operand.as_str(), None),
+        };
+
+        let (value, digits) = parse_hexadecimal(address)?;
+
+        let (zero_page_mode, absolute_mode) = match index {
+            None => (AddressingMode::ZeroPage, AddressingMode::Absolute),
+            Some("X") => (AddressingMode::ZeroPageX, AddressingMode::AbsoluteX),
+            Some("Y") => (AddressingMode::ZeroPageY, AddressingMode::AbsoluteY),
+            Some(_) => return None,
+        };
+
+        if index.is_none() && supports(AddressingMode::Relative) {
+            (AddressingMode::Relative, value, 2)
+        } else if digits <= 2 && supports(zero_page_mode) {
+            (zero_page_mode, value, 1)
+        } else {
+            (absolute_mode, value, 2)
+        }
+    };
+
+    if !supports(mode) {
+        return None;
+    }
+
+    Some((mode, value.to_le_bytes()[..digits].to_vec()))
+}
+
+/// Parses a `$` prefixed hexadecimal number of 1 to 4 digits and returns it with its
+/// number of digits.
+fn parse_hexadecimal(value: &str) -> Option<(u16, usize)> {
+    let digits = value.strip_prefix('$')?;
+
+    if digits.is_empty() || digits.len() > 4 {
+        return None;
+    }
+
+    Some((u16::from_str_radix(digits, 16).ok()?, digits.len()))
+}
+
+fn parse_zero_page(value: &str) -> Option<u16> {
+    parse_hexadecimal(value)
+        .filter(|(value, _)| *value <= 0xFF)
+        .map(|(value, _)| value)
+}
+
 const ADC_INSTRUCTION: &str = "ADC";
+const ALR_INSTRUCTION: &str = "ALR";
+const ANC_INSTRUCTION: &str = "ANC";
 const AND_INSTRUCTION: &str = "AND";
+const ARR_INSTRUCTION: &str = "ARR";
 const ASL_INSTRUCTION: &str = "ASL";
 const BCC_INSTRUCTION: &str = "BCC";
 const BCS_INSTRUCTION: &str = "BCS";
@@ -96,6 +326,7 @@ const CLV_INSTRUCTION: &str = "CLV";
 const CMP_INSTRUCTION: &str = "CMP";
 const CPX_INSTRUCTION: &str = "CPX";
 const CPY_INSTRUCTION: &str = "CPY";
+const DCP_INSTRUCTION: &str = "DCP";
 const DEC_INSTRUCTION: &str = "DEC";
 const DEX_INSTRUCTION: &str = "DEX";
 const DEY_INSTRUCTION: &str = "DEY";
@@ -103,8 +334,10 @@ const EOR_INSTRUCTION: &str = "EOR";
 const INC_INSTRUCTION: &str = "INC";
 const INX_INSTRUCTION: &str = "INX";
 const INY_INSTRUCTION: &str = "INY";
+const ISC_INSTRUCTION: &str = "ISC";
 const JMP_INSTRUCTION: &str = "JMP";
 const JSR_INSTRUCTION: &str = "JSR";
+const LAX_INSTRUCTION: &str = "LAX";
 const LDA_INSTRUCTION: &str = "LDA";
 const LDX_INSTRUCTION: &str = "LDX";
 const LDY_INSTRUCTION: &str = "LDY";
@@ -115,14 +348,20 @@ const PHA_INSTRUCTION: &str = "PHA";
 const PHP_INSTRUCTION: &str = "PHP";
 const PLA_INSTRUCTION: &str = "PLA";
 const PLP_INSTRUCTION: &str = "PLP";
+const RLA_INSTRUCTION: &str = "RLA";
 const ROL_INSTRUCTION: &str = "ROL";
 const ROR_INSTRUCTION: &str = "ROR";
+const RRA_INSTRUCTION: &str = "RRA";
 const RTI_INSTRUCTION: &str = "RTI";
 const RTS_INSTRUCTION: &str = "RTS";
+const SAX_INSTRUCTION: &str = "SAX";
 const SBC_INSTRUCTION: &str = "SBC";
+const SBX_INSTRUCTION: &str = "SBX";
 const SEC_INSTRUCTION: &str = "SEC";
 const SED_INSTRUCTION: &str = "SED";
 const SEI_INSTRUCTION: &str = "SEI";
+const SLO_INSTRUCTION: &str = "SLO";
+const SRE_INSTRUCTION: &str = "SRE";
 const STA_INSTRUCTION: &str = "STA";
 const STX_INSTRUCTION: &str = "STX";
 const STY_INSTRUCTION: &str = "STY";
@@ -133,7 +372,7 @@ const TXA_INSTRUCTION: &str = "TXA";
 const TXS_INSTRUCTION: &str = "TXS";
 const TYA_INSTRUCTION: &str = "TYA";
 
-pub const INSTRUCTION_SET: [Instruction; 151] = [
+pub const INSTRUCTION_SET: [Instruction; 235] = [
     Instruction {
         opcode: 0x00,
         mnemonic: BRK_INSTRUCTION,
@@ -141,6 +380,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 7,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::brk_instruction,
     },
     Instruction {
@@ -150,8 +390,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ora_instruction,
     },
+    Instruction {
+        opcode: 0x03,
+        mnemonic: SLO_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::slo_instruction,
+    },
+    Instruction {
+        opcode: 0x04,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 3,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x05,
         mnemonic: ORA_INSTRUCTION,
@@ -159,6 +420,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ora_instruction,
     },
     Instruction {
@@ -168,8 +430,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::asl_instruction,
     },
+    Instruction {
+        opcode: 0x07,
+        mnemonic: SLO_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 5,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::slo_instruction,
+    },
     Instruction {
         opcode: 0x08,
         mnemonic: PHP_INSTRUCTION,
@@ -177,6 +450,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::php_instruction,
     },
     Instruction {
@@ -186,6 +460,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ora_instruction,
     },
     Instruction {
@@ -195,8 +470,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Accumulator,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::asl_instruction,
     },
+    Instruction {
+        opcode: 0x0B,
+        mnemonic: ANC_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::anc_instruction,
+    },
+    Instruction {
+        opcode: 0x0C,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x0D,
         mnemonic: ORA_INSTRUCTION,
@@ -204,6 +500,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ora_instruction,
     },
     Instruction {
@@ -213,8 +510,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::asl_instruction,
     },
+    Instruction {
+        opcode: 0x0F,
+        mnemonic: SLO_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::slo_instruction,
+    },
     Instruction {
         opcode: 0x10,
         mnemonic: BPL_INSTRUCTION,
@@ -222,6 +530,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Relative,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::bpl_instruction,
     },
     Instruction {
@@ -231,8 +540,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::IndirectY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ora_instruction,
     },
+    Instruction {
+        opcode: 0x13,
+        mnemonic: SLO_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::slo_instruction,
+    },
+    Instruction {
+        opcode: 0x14,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x15,
         mnemonic: ORA_INSTRUCTION,
@@ -240,6 +570,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ora_instruction,
     },
     Instruction {
@@ -249,8 +580,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::asl_instruction,
     },
+    Instruction {
+        opcode: 0x17,
+        mnemonic: SLO_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::slo_instruction,
+    },
     Instruction {
         opcode: 0x18,
         mnemonic: CLC_INSTRUCTION,
@@ -258,6 +600,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::clc_instruction,
     },
     Instruction {
@@ -267,8 +610,39 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ora_instruction,
     },
+    Instruction {
+        opcode: 0x1A,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 1,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Implied,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0x1B,
+        mnemonic: SLO_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::slo_instruction,
+    },
+    Instruction {
+        opcode: 0x1C,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x1D,
         mnemonic: ORA_INSTRUCTION,
@@ -276,6 +650,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ora_instruction,
     },
     Instruction {
@@ -285,8 +660,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 7,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::asl_instruction,
     },
+    Instruction {
+        opcode: 0x1F,
+        mnemonic: SLO_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::slo_instruction,
+    },
     Instruction {
         opcode: 0x20,
         mnemonic: JSR_INSTRUCTION,
@@ -294,6 +680,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::jsr_instruction,
     },
     Instruction {
@@ -303,8 +690,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::and_instruction,
     },
+    Instruction {
+        opcode: 0x23,
+        mnemonic: RLA_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rla_instruction,
+    },
     Instruction {
         opcode: 0x24,
         mnemonic: BIT_INSTRUCTION,
@@ -312,6 +710,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::bit_instruction,
     },
     Instruction {
@@ -321,6 +720,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::and_instruction,
     },
     Instruction {
@@ -330,8 +730,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::rol_instruction,
     },
+    Instruction {
+        opcode: 0x27,
+        mnemonic: RLA_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 5,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rla_instruction,
+    },
     Instruction {
         opcode: 0x28,
         mnemonic: PLP_INSTRUCTION,
@@ -339,6 +750,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::plp_instruction,
     },
     Instruction {
@@ -348,6 +760,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::and_instruction,
     },
     Instruction {
@@ -357,8 +770,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Accumulator,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::rol_instruction,
     },
+    Instruction {
+        opcode: 0x2B,
+        mnemonic: ANC_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::anc_instruction,
+    },
     Instruction {
         opcode: 0x2C,
         mnemonic: BIT_INSTRUCTION,
@@ -366,6 +790,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::bit_instruction,
     },
     Instruction {
@@ -375,6 +800,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::and_instruction,
     },
     Instruction {
@@ -384,8 +810,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::rol_instruction,
     },
+    Instruction {
+        opcode: 0x2F,
+        mnemonic: RLA_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rla_instruction,
+    },
     Instruction {
         opcode: 0x30,
         mnemonic: BMI_INSTRUCTION,
@@ -393,6 +830,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Relative,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::bmi_instruction,
     },
     Instruction {
@@ -402,8 +840,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::IndirectY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::and_instruction,
     },
+    Instruction {
+        opcode: 0x33,
+        mnemonic: RLA_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rla_instruction,
+    },
+    Instruction {
+        opcode: 0x34,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x35,
         mnemonic: AND_INSTRUCTION,
@@ -411,6 +870,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::and_instruction,
     },
     Instruction {
@@ -420,8 +880,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::rol_instruction,
     },
+    Instruction {
+        opcode: 0x37,
+        mnemonic: RLA_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rla_instruction,
+    },
     Instruction {
         opcode: 0x38,
         mnemonic: SEC_INSTRUCTION,
@@ -429,6 +900,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sec_instruction,
     },
     Instruction {
@@ -438,8 +910,39 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::and_instruction,
     },
+    Instruction {
+        opcode: 0x3A,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 1,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Implied,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0x3B,
+        mnemonic: RLA_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rla_instruction,
+    },
+    Instruction {
+        opcode: 0x3C,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x3d,
         mnemonic: AND_INSTRUCTION,
@@ -447,6 +950,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::and_instruction,
     },
     Instruction {
@@ -456,8 +960,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 7,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::rol_instruction,
     },
+    Instruction {
+        opcode: 0x3F,
+        mnemonic: RLA_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rla_instruction,
+    },
     Instruction {
         opcode: 0x40,
         mnemonic: RTI_INSTRUCTION,
@@ -465,6 +980,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::rti_instruction,
     },
     Instruction {
@@ -474,8 +990,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::eor_instruction,
     },
+    Instruction {
+        opcode: 0x43,
+        mnemonic: SRE_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sre_instruction,
+    },
+    Instruction {
+        opcode: 0x44,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 3,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x45,
         mnemonic: EOR_INSTRUCTION,
@@ -483,6 +1020,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::eor_instruction,
     },
     Instruction {
@@ -492,8 +1030,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lsr_instruction,
     },
+    Instruction {
+        opcode: 0x47,
+        mnemonic: SRE_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 5,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sre_instruction,
+    },
     Instruction {
         opcode: 0x48,
         mnemonic: PHA_INSTRUCTION,
@@ -501,6 +1050,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::pha_instruction,
     },
     Instruction {
@@ -510,6 +1060,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::eor_instruction,
     },
     Instruction {
@@ -519,8 +1070,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Accumulator,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lsr_instruction,
     },
+    Instruction {
+        opcode: 0x4B,
+        mnemonic: ALR_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::alr_instruction,
+    },
     Instruction {
         opcode: 0x4C,
         mnemonic: JMP_INSTRUCTION,
@@ -528,6 +1090,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::jmp_instruction,
     },
     Instruction {
@@ -537,6 +1100,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::eor_instruction,
     },
     Instruction {
@@ -546,8 +1110,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lsr_instruction,
     },
+    Instruction {
+        opcode: 0x4F,
+        mnemonic: SRE_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sre_instruction,
+    },
     Instruction {
         opcode: 0x50,
         mnemonic: BVC_INSTRUCTION,
@@ -555,6 +1130,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Relative,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::bvc_instruction,
     },
     Instruction {
@@ -564,8 +1140,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::IndirectY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::eor_instruction,
     },
+    Instruction {
+        opcode: 0x53,
+        mnemonic: SRE_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sre_instruction,
+    },
+    Instruction {
+        opcode: 0x54,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x55,
         mnemonic: EOR_INSTRUCTION,
@@ -573,6 +1170,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::eor_instruction,
     },
     Instruction {
@@ -582,8 +1180,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lsr_instruction,
     },
+    Instruction {
+        opcode: 0x57,
+        mnemonic: SRE_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sre_instruction,
+    },
     Instruction {
         opcode: 0x58,
         mnemonic: CLI_INSTRUCTION,
@@ -591,6 +1200,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cli_instruction,
     },
     Instruction {
@@ -600,8 +1210,39 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::eor_instruction,
     },
+    Instruction {
+        opcode: 0x5A,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 1,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Implied,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0x5B,
+        mnemonic: SRE_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sre_instruction,
+    },
+    Instruction {
+        opcode: 0x5C,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x5D,
         mnemonic: EOR_INSTRUCTION,
@@ -609,6 +1250,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::eor_instruction,
     },
     Instruction {
@@ -618,8 +1260,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 7,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lsr_instruction,
     },
+    Instruction {
+        opcode: 0x5F,
+        mnemonic: SRE_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sre_instruction,
+    },
     Instruction {
         opcode: 0x60,
         mnemonic: RTS_INSTRUCTION,
@@ -627,6 +1280,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::rts_instruction,
     },
     Instruction {
@@ -636,8 +1290,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::adc_instruction,
     },
+    Instruction {
+        opcode: 0x63,
+        mnemonic: RRA_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rra_instruction,
+    },
+    Instruction {
+        opcode: 0x64,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 3,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x65,
         mnemonic: ADC_INSTRUCTION,
@@ -645,6 +1320,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::adc_instruction,
     },
     Instruction {
@@ -654,8 +1330,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ror_instruction,
     },
+    Instruction {
+        opcode: 0x67,
+        mnemonic: RRA_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 5,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rra_instruction,
+    },
     Instruction {
         opcode: 0x68,
         mnemonic: PLA_INSTRUCTION,
@@ -663,6 +1350,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::pla_instruction,
     },
     Instruction {
@@ -672,6 +1360,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::adc_instruction,
     },
     Instruction {
@@ -681,8 +1370,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Accumulator,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ror_instruction,
     },
+    Instruction {
+        opcode: 0x6B,
+        mnemonic: ARR_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::arr_instruction,
+    },
     Instruction {
         opcode: 0x6C,
         mnemonic: JMP_INSTRUCTION,
@@ -690,6 +1390,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::Indirect,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::jmp_instruction,
     },
     Instruction {
@@ -699,6 +1400,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::adc_instruction,
     },
     Instruction {
@@ -708,8 +1410,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ror_instruction,
     },
+    Instruction {
+        opcode: 0x6F,
+        mnemonic: RRA_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rra_instruction,
+    },
     Instruction {
         opcode: 0x70,
         mnemonic: BVS_INSTRUCTION,
@@ -717,6 +1430,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Relative,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::bvs_instruction,
     },
     Instruction {
@@ -726,8 +1440,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::IndirectY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::adc_instruction,
     },
+    Instruction {
+        opcode: 0x73,
+        mnemonic: RRA_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rra_instruction,
+    },
+    Instruction {
+        opcode: 0x74,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x75,
         mnemonic: ADC_INSTRUCTION,
@@ -735,6 +1470,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::adc_instruction,
     },
     Instruction {
@@ -744,8 +1480,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ror_instruction,
     },
+    Instruction {
+        opcode: 0x77,
+        mnemonic: RRA_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rra_instruction,
+    },
     Instruction {
         opcode: 0x78,
         mnemonic: SEI_INSTRUCTION,
@@ -753,6 +1500,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sei_instruction,
     },
     Instruction {
@@ -762,8 +1510,39 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::adc_instruction,
     },
+    Instruction {
+        opcode: 0x7A,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 1,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Implied,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0x7B,
+        mnemonic: RRA_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rra_instruction,
+    },
+    Instruction {
+        opcode: 0x7C,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x7D,
         mnemonic: ADC_INSTRUCTION,
@@ -771,6 +1550,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::adc_instruction,
     },
     Instruction {
@@ -780,8 +1560,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 7,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ror_instruction,
     },
+    Instruction {
+        opcode: 0x7F,
+        mnemonic: RRA_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::rra_instruction,
+    },
+    Instruction {
+        opcode: 0x80,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0x81,
         mnemonic: STA_INSTRUCTION,
@@ -789,8 +1590,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sta_instruction,
     },
+    Instruction {
+        opcode: 0x82,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0x83,
+        mnemonic: SAX_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::IndirectX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sax_instruction,
+    },
     Instruction {
         opcode: 0x84,
         mnemonic: STY_INSTRUCTION,
@@ -798,6 +1620,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sty_instruction,
     },
     Instruction {
@@ -807,6 +1630,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sta_instruction,
     },
     Instruction {
@@ -816,16 +1640,38 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::stx_instruction,
     },
+    Instruction {
+        opcode: 0x87,
+        mnemonic: SAX_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 3,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sax_instruction,
+    },
     Instruction {
         opcode: 0x88,
         mnemonic: DEY_INSTRUCTION,
         bytes: 1,
         clock_periods: 2,
-        addressing_mode: AddressingMode::Implied,
+        addressing_mode: AddressingMode::Implied,
+        sets_program_counter: false,
+        is_documented: true,
+        execute: Cpu::dey_instruction,
+    },
+    Instruction {
+        opcode: 0x89,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
-        execute: Cpu::dey_instruction,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
     },
     Instruction {
         opcode: 0x8A,
@@ -834,6 +1680,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::txa_instruction,
     },
     Instruction {
@@ -843,6 +1690,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sty_instruction,
     },
     Instruction {
@@ -852,6 +1700,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sta_instruction,
     },
     Instruction {
@@ -861,8 +1710,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::stx_instruction,
     },
+    Instruction {
+        opcode: 0x8F,
+        mnemonic: SAX_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sax_instruction,
+    },
     Instruction {
         opcode: 0x90,
         mnemonic: BCC_INSTRUCTION,
@@ -870,6 +1730,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Relative,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::bcc_instruction,
     },
     Instruction {
@@ -879,6 +1740,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sta_instruction,
     },
     Instruction {
@@ -888,6 +1750,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sty_instruction,
     },
     Instruction {
@@ -897,6 +1760,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sta_instruction,
     },
     Instruction {
@@ -906,8 +1770,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::stx_instruction,
     },
+    Instruction {
+        opcode: 0x97,
+        mnemonic: SAX_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::ZeroPageY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sax_instruction,
+    },
     Instruction {
         opcode: 0x98,
         mnemonic: TYA_INSTRUCTION,
@@ -915,6 +1790,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::tya_instruction,
     },
     Instruction {
@@ -924,6 +1800,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sta_instruction,
     },
     Instruction {
@@ -933,6 +1810,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::txs_instruction,
     },
     Instruction {
@@ -942,6 +1820,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sta_instruction,
     },
     Instruction {
@@ -951,6 +1830,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldy_instruction,
     },
     Instruction {
@@ -960,6 +1840,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lda_instruction,
     },
     Instruction {
@@ -969,8 +1850,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldx_instruction,
     },
+    Instruction {
+        opcode: 0xA3,
+        mnemonic: LAX_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::IndirectX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::lax_instruction,
+    },
     Instruction {
         opcode: 0xA4,
         mnemonic: LDY_INSTRUCTION,
@@ -978,6 +1870,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldy_instruction,
     },
     Instruction {
@@ -987,6 +1880,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lda_instruction,
     },
     Instruction {
@@ -996,8 +1890,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldx_instruction,
     },
+    Instruction {
+        opcode: 0xA7,
+        mnemonic: LAX_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 3,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::lax_instruction,
+    },
     Instruction {
         opcode: 0xA8,
         mnemonic: TAY_INSTRUCTION,
@@ -1005,6 +1910,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::tay_instruction,
     },
     Instruction {
@@ -1014,6 +1920,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lda_instruction,
     },
     Instruction {
@@ -1023,6 +1930,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::tax_instruction,
     },
     Instruction {
@@ -1032,6 +1940,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldy_instruction,
     },
     Instruction {
@@ -1041,6 +1950,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lda_instruction,
     },
     Instruction {
@@ -1050,8 +1960,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldx_instruction,
     },
+    Instruction {
+        opcode: 0xAF,
+        mnemonic: LAX_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::lax_instruction,
+    },
     Instruction {
         opcode: 0xB0,
         mnemonic: BCS_INSTRUCTION,
@@ -1059,6 +1980,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Relative,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::bcs_instruction,
     },
     Instruction {
@@ -1068,8 +1990,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::IndirectY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lda_instruction,
     },
+    Instruction {
+        opcode: 0xB3,
+        mnemonic: LAX_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 5,
+        addressing_mode: AddressingMode::IndirectY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::lax_instruction,
+    },
     Instruction {
         opcode: 0xB4,
         mnemonic: LDY_INSTRUCTION,
@@ -1077,6 +2010,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldy_instruction,
     },
     Instruction {
@@ -1086,6 +2020,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lda_instruction,
     },
     Instruction {
@@ -1095,8 +2030,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldx_instruction,
     },
+    Instruction {
+        opcode: 0xB7,
+        mnemonic: LAX_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::ZeroPageY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::lax_instruction,
+    },
     Instruction {
         opcode: 0xB8,
         mnemonic: CLV_INSTRUCTION,
@@ -1104,6 +2050,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::clv_instruction,
     },
     Instruction {
@@ -1113,6 +2060,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lda_instruction,
     },
     Instruction {
@@ -1122,6 +2070,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::tsx_instruction,
     },
     Instruction {
@@ -1131,6 +2080,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldy_instruction,
     },
     Instruction {
@@ -1140,6 +2090,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::lda_instruction,
     },
     Instruction {
@@ -1149,8 +2100,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::ldx_instruction,
     },
+    Instruction {
+        opcode: 0xBF,
+        mnemonic: LAX_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::AbsoluteY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::lax_instruction,
+    },
     Instruction {
         opcode: 0xC0,
         mnemonic: CPY_INSTRUCTION,
@@ -1158,6 +2120,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cpy_instruction,
     },
     Instruction {
@@ -1167,8 +2130,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cmp_instruction,
     },
+    Instruction {
+        opcode: 0xC2,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0xC3,
+        mnemonic: DCP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::dcp_instruction,
+    },
     Instruction {
         opcode: 0xC4,
         mnemonic: CPY_INSTRUCTION,
@@ -1176,6 +2160,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cpy_instruction,
     },
     Instruction {
@@ -1185,6 +2170,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cmp_instruction,
     },
     Instruction {
@@ -1194,8 +2180,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::dec_instruction,
     },
+    Instruction {
+        opcode: 0xC7,
+        mnemonic: DCP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 5,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::dcp_instruction,
+    },
     Instruction {
         opcode: 0xC8,
         mnemonic: INY_INSTRUCTION,
@@ -1203,6 +2200,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::iny_instruction,
     },
     Instruction {
@@ -1212,6 +2210,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cmp_instruction,
     },
     Instruction {
@@ -1221,8 +2220,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::dex_instruction,
     },
+    Instruction {
+        opcode: 0xCB,
+        mnemonic: SBX_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::sbx_instruction,
+    },
     Instruction {
         opcode: 0xCC,
         mnemonic: CPY_INSTRUCTION,
@@ -1230,6 +2240,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cpy_instruction,
     },
     Instruction {
@@ -1239,6 +2250,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cmp_instruction,
     },
     Instruction {
@@ -1248,8 +2260,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::dec_instruction,
     },
+    Instruction {
+        opcode: 0xCF,
+        mnemonic: DCP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::dcp_instruction,
+    },
     Instruction {
         opcode: 0xD0,
         mnemonic: BNE_INSTRUCTION,
@@ -1257,6 +2280,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Relative,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::bne_instruction,
     },
     Instruction {
@@ -1266,8 +2290,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::IndirectY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cmp_instruction,
     },
+    Instruction {
+        opcode: 0xD3,
+        mnemonic: DCP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::dcp_instruction,
+    },
+    Instruction {
+        opcode: 0xD4,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0xD5,
         mnemonic: CMP_INSTRUCTION,
@@ -1275,6 +2320,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cmp_instruction,
     },
     Instruction {
@@ -1284,8 +2330,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::dec_instruction,
     },
+    Instruction {
+        opcode: 0xD7,
+        mnemonic: DCP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::dcp_instruction,
+    },
     Instruction {
         opcode: 0xD8,
         mnemonic: CLD_INSTRUCTION,
@@ -1293,6 +2350,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cld_instruction,
     },
     Instruction {
@@ -1302,8 +2360,39 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cmp_instruction,
     },
+    Instruction {
+        opcode: 0xDA,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 1,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Implied,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0xDB,
+        mnemonic: DCP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::dcp_instruction,
+    },
+    Instruction {
+        opcode: 0xDC,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0xDD,
         mnemonic: CMP_INSTRUCTION,
@@ -1311,6 +2400,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cmp_instruction,
     },
     Instruction {
@@ -1320,8 +2410,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 7,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::dec_instruction,
     },
+    Instruction {
+        opcode: 0xDF,
+        mnemonic: DCP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::dcp_instruction,
+    },
     Instruction {
         opcode: 0xE0,
         mnemonic: CPX_INSTRUCTION,
@@ -1329,6 +2430,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cpx_instruction,
     },
     Instruction {
@@ -1338,8 +2440,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::IndirectX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sbc_instruction,
     },
+    Instruction {
+        opcode: 0xE2,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0xE3,
+        mnemonic: ISC_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::isc_instruction,
+    },
     Instruction {
         opcode: 0xE4,
         mnemonic: CPX_INSTRUCTION,
@@ -1347,6 +2470,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cpx_instruction,
     },
     Instruction {
@@ -1356,6 +2480,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 3,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sbc_instruction,
     },
     Instruction {
@@ -1365,8 +2490,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::ZeroPage,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::inc_instruction,
     },
+    Instruction {
+        opcode: 0xE7,
+        mnemonic: ISC_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 5,
+        addressing_mode: AddressingMode::ZeroPage,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::isc_instruction,
+    },
     Instruction {
         opcode: 0xE8,
         mnemonic: INX_INSTRUCTION,
@@ -1374,6 +2510,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::inx_instruction,
     },
     Instruction {
@@ -1383,6 +2520,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Immediate,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sbc_instruction,
     },
     Instruction {
@@ -1392,6 +2530,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::nop_instruction,
     },
     Instruction {
@@ -1401,6 +2540,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::cpx_instruction,
     },
     Instruction {
@@ -1410,6 +2550,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sbc_instruction,
     },
     Instruction {
@@ -1419,8 +2560,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::Absolute,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::inc_instruction,
     },
+    Instruction {
+        opcode: 0xEF,
+        mnemonic: ISC_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::Absolute,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::isc_instruction,
+    },
     Instruction {
         opcode: 0xF0,
         mnemonic: BEQ_INSTRUCTION,
@@ -1428,6 +2580,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Relative,
         sets_program_counter: true,
+        is_documented: true,
         execute: Cpu::beq_instruction,
     },
     Instruction {
@@ -1437,8 +2590,29 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 5,
         addressing_mode: AddressingMode::IndirectY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sbc_instruction,
     },
+    Instruction {
+        opcode: 0xF3,
+        mnemonic: ISC_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 8,
+        addressing_mode: AddressingMode::IndirectY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::isc_instruction,
+    },
+    Instruction {
+        opcode: 0xF4,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0xF5,
         mnemonic: SBC_INSTRUCTION,
@@ -1446,6 +2620,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sbc_instruction,
     },
     Instruction {
@@ -1455,8 +2630,19 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 6,
         addressing_mode: AddressingMode::ZeroPageX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::inc_instruction,
     },
+    Instruction {
+        opcode: 0xF7,
+        mnemonic: ISC_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 6,
+        addressing_mode: AddressingMode::ZeroPageX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::isc_instruction,
+    },
     Instruction {
         opcode: 0xF8,
         mnemonic: SED_INSTRUCTION,
@@ -1464,6 +2650,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 2,
         addressing_mode: AddressingMode::Implied,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sed_instruction,
     },
     Instruction {
@@ -1473,8 +2660,39 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteY,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sbc_instruction,
     },
+    Instruction {
+        opcode: 0xFA,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 1,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Implied,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
+    Instruction {
+        opcode: 0xFB,
+        mnemonic: ISC_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteY,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::isc_instruction,
+    },
+    Instruction {
+        opcode: 0xFC,
+        mnemonic: NOP_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 4,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::nop_instruction,
+    },
     Instruction {
         opcode: 0xFD,
         mnemonic: SBC_INSTRUCTION,
@@ -1482,6 +2700,7 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 4,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::sbc_instruction,
     },
     Instruction {
@@ -1491,14 +2710,209 @@ pub const INSTRUCTION_SET: [Instruction; 151] = [
         clock_periods: 7,
         addressing_mode: AddressingMode::AbsoluteX,
         sets_program_counter: false,
+        is_documented: true,
         execute: Cpu::inc_instruction,
     },
+    Instruction {
+        opcode: 0xFF,
+        mnemonic: ISC_INSTRUCTION,
+        bytes: 3,
+        clock_periods: 7,
+        addressing_mode: AddressingMode::AbsoluteX,
+        sets_program_counter: false,
+        is_documented: false,
+        execute: Cpu::isc_instruction,
+    },
+];
+
+/// Instructions the WDC 65C02 adds to, or changes from, `INSTRUCTION_SET`.
+pub const WDC_65C02_INSTRUCTION_SET: [Instruction; 1] = [
+    Instruction {
+        opcode: 0x89,
+        mnemonic: BIT_INSTRUCTION,
+        bytes: 2,
+        clock_periods: 2,
+        addressing_mode: AddressingMode::Immediate,
+        sets_program_counter: false,
+        is_documented: true,
+        execute: Cpu::bit_instruction,
+    },
 ];
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_reads_and_writes_memory() {
+        let lda_immediate = Instruction::binary_search(0xA9).unwrap();
+        let lda_absolute = Instruction::binary_search(0xAD).unwrap();
+        let sta_absolute = Instruction::binary_search(0x8D).unwrap();
+        let sax_absolute = Instruction::binary_search(0x8F).unwrap();
+        let inc_zero_page = Instruction::binary_search(0xE6).unwrap();
+        let jmp_absolute = Instruction::binary_search(0x4C).unwrap();
+
+        assert!(!lda_immediate.reads_memory() && !lda_immediate.writes_memory());
+        assert!(lda_absolute.reads_memory() && !lda_absolute.writes_memory());
+        assert!(!sta_absolute.reads_memory() && sta_absolute.writes_memory());
+        assert!(!sax_absolute.reads_memory() && sax_absolute.writes_memory());
+        assert!(inc_zero_page.reads_memory() && inc_zero_page.writes_memory());
+        assert!(!jmp_absolute.reads_memory() && !jmp_absolute.writes_memory());
+    }
+
+    #[test]
+    fn test_is_jam_opcode() {
+        assert!(is_jam_opcode(0x02));
+        assert!(is_jam_opcode(0x12));
+        assert!(is_jam_opcode(0xF2));
+        assert!(!is_jam_opcode(0xEA));
+        assert!(!is_jam_opcode(0x82));
+
+        for opcode in JAM_OPCODES {
+            assert!(Instruction::binary_search(opcode).is_none());
+        }
+    }
+
+    #[test]
+    fn test_lookup_by_variant() {
+        assert_eq!(Instruction::lookup(0x89, CpuVariant::Nmos6502).unwrap().mnemonic, NOP_INSTRUCTION);
+        assert_eq!(Instruction::lookup(0x89, CpuVariant::Wdc65C02).unwrap().mnemonic, BIT_INSTRUCTION);
+        assert_eq!(Instruction::lookup(0xA9, CpuVariant::Wdc65C02).unwrap().mnemonic, LDA_INSTRUCTION);
+        assert_eq!(Instruction::lookup(0xA7, CpuVariant::Nmos6502).unwrap().mnemonic, LAX_INSTRUCTION);
+        assert!(Instruction::lookup(0xA7, CpuVariant::Wdc65C02).is_none());
+        assert!(Instruction::lookup(0x1A, CpuVariant::Wdc65C02).is_none());
+    }
+
+    #[test]
+    fn test_parse_operand() {
+        let cases: [(&str, &str, AddressingMode, Vec<u8>); 15] = [
+            ("ASL", "", AddressingMode::Accumulator, vec![]),
+            ("ROR", "A", AddressingMode::Accumulator, vec![]),
+            ("INX", "", AddressingMode::Implied, vec![]),
+            ("LDA", "#$12", AddressingMode::Immediate, vec![0x12]),
+            ("LDA", "$12", AddressingMode::ZeroPage, vec![0x12]),
+            ("LDA", "$0012", AddressingMode::Absolute, vec![0x12, 0x00]),
+            ("LDA", "$1234", AddressingMode::Absolute, vec![0x34, 0x12]),
+            ("lda", "$12, x", AddressingMode::ZeroPageX, vec![0x12]),
+            ("LDX", "$12,Y", AddressingMode::ZeroPageY, vec![0x12]),
+            ("LDA", "$12,Y", AddressingMode::AbsoluteY, vec![0x12, 0x00]),
+            ("LDA", "$1234,X", AddressingMode::AbsoluteX, vec![0x34, 0x12]),
+            ("LDA", "($12,X)", AddressingMode::IndirectX, vec![0x12]),
+            ("LDA", "($12),Y", AddressingMode::IndirectY, vec![0x12]),
+            ("JMP", "($1234)", AddressingMode::Indirect, vec![0x34, 0x12]),
+            ("BNE", "$8000", AddressingMode::Relative, vec![0x00, 0x80]),
+        ];
+
+        for (mnemonic, operand, mode, bytes) in cases {
+            assert_eq!(
+                parse_operand(mnemonic, operand),
+                Some((mode, bytes)),
+                "{} {}",
+                mnemonic,
+                operand
+            );
+        }
+
+        assert_eq!(parse_operand("JMP", "$12"), Some((AddressingMode::Absolute, vec![0x12, 0x00])));
+
+        assert_eq!(parse_operand("LDA", "#$123"), None);
+        assert_eq!(parse_operand("LDA", "($1234,X)"), None);
+        assert_eq!(parse_operand("STA", "#$12"), None);
+        assert_eq!(parse_operand("LDA", "$12,Z"), None);
+        assert_eq!(parse_operand("LDA", "12"), None);
+        assert_eq!(parse_operand("XYZ", "$12"), None);
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(Instruction::encode("NOP", AddressingMode::Implied, &[]), Some(vec![0xEA]));
+        assert_eq!(
+            Instruction::encode("lda", AddressingMode::Immediate, &[0x12]),
+            Some(vec![0xA9, 0x12])
+        );
+        assert_eq!(Instruction::encode("ASL", AddressingMode::Accumulator, &[]), Some(vec![0x0A]));
+
+        let (mode, operand) = parse_operand("JMP", "($FFFC)").unwrap();
+
+        assert_eq!(
+            Instruction::encode("JMP", mode, &operand),
+            Some(vec![0x6C, 0xFC, 0xFF])
+        );
+
+        assert_eq!(Instruction::encode("LDA", AddressingMode::Immediate, &[0x12, 0x00]), None);
+        assert_eq!(Instruction::encode("STA", AddressingMode::Immediate, &[0x12]), None);
+        assert_eq!(Instruction::encode("BIT", AddressingMode::Immediate, &[0x12]), None);
+    }
+
+    #[test]
+    fn test_encode_round_trips_every_opcode() {
+        for instruction in INSTRUCTION_SET {
+            let operand: Vec<u8> = (1..instruction.bytes).collect();
+
+            let bytes =
+                Instruction::encode(instruction.mnemonic, instruction.addressing_mode, &operand)
+                    .unwrap();
+
+            let encodings = INSTRUCTION_SET
+                .iter()
+                .filter(|other| {
+                    other.mnemonic == instruction.mnemonic
+                        && other.addressing_mode == instruction.addressing_mode
+                })
+                .count();
+
+            // When several opcodes encode the same instruction, only one of them is chosen.
+            if encodings == 1 {
+                assert_eq!(bytes[0], instruction.opcode, "{}", instruction.mnemonic);
+            }
+
+            assert_eq!(bytes[1..], operand[..]);
+
+            let decoded = Instruction::binary_search(bytes[0]).unwrap();
+
+            assert_eq!(decoded.mnemonic, instruction.mnemonic);
+            assert_eq!(decoded.addressing_mode, instruction.addressing_mode);
+        }
+    }
+
+    #[test]
+    fn test_is_documented() {
+        let undocumented: Vec<&str> = INSTRUCTION_SET
+            .iter()
+            .filter(|instruction| !instruction.is_documented)
+            .map(|instruction| instruction.mnemonic)
+            .collect();
+
+        assert_eq!(INSTRUCTION_SET.len() - undocumented.len(), 151);
+
+        for mnemonic in undocumented {
+            assert!(
+                matches!(
+                    mnemonic,
+                    ALR_INSTRUCTION
+                        | ANC_INSTRUCTION
+                        | ARR_INSTRUCTION
+                        | DCP_INSTRUCTION
+                        | ISC_INSTRUCTION
+                        | LAX_INSTRUCTION
+                        | NOP_INSTRUCTION
+                        | RLA_INSTRUCTION
+                        | RRA_INSTRUCTION
+                        | SAX_INSTRUCTION
+                        | SBX_INSTRUCTION
+                        | SLO_INSTRUCTION
+                        | SRE_INSTRUCTION
+                ),
+                "{} isn't undocumented",
+                mnemonic
+            );
+        }
+
+        assert!(Instruction::binary_search(0xA9).unwrap().is_documented);
+        assert!(!Instruction::binary_search(0xA7).unwrap().is_documented);
+        assert!(WDC_65C02_INSTRUCTION_SET.iter().all(|instruction| instruction.is_documented));
+    }
+
     #[test]
     fn test_instruction_set_is_in_order() {
         for i in 0..INSTRUCTION_SET.len() -1 {
@@ -0,0 +1,8 @@
+/// The interrupt lines of the 6502.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterruptKind {
+    /// The non-maskable interrupt line.
+    Nmi,
+    /// The maskable interrupt line.
+    Irq,
+}
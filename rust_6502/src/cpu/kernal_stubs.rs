@@ -0,0 +1,13 @@
+/// Closures that stand in for common Commodore KERNAL routines.
+///
+/// Any closure left as `None` is still installed as a plain `RTS` stub so that
+/// programs calling the routine return immediately.
+#[derive(Default)]
+pub struct KernalStubs {
+    /// Called by `CHROUT` ($FFD2) with the character in the accumulator.
+    pub chrout: Option<Box<dyn FnMut(u8)>>,
+    /// Called by `CHRIN` ($FFCF). The returned character is loaded into the accumulator.
+    pub chrin: Option<Box<dyn FnMut() -> u8>>,
+    /// Called by `STOP` ($FFE1). Returning `true` sets the zero flag to signal that STOP was pressed.
+    pub stop: Option<Box<dyn FnMut() -> bool>>,
+}
@@ -1,16 +1,83 @@
+use std::cell::RefCell;
+use std::fmt::Display;
 use std::fs::File;
 use std::io::Read;
+use std::rc::Rc;
 
 const SIXTY_FOUR_K_BYTES: usize = 64 * 1024;
 
+/// The reasons loading a file into memory can fail.
+#[derive(Debug)]
+pub enum MemoryLoadError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+    /// The data doesn't fit between the starting address and the top of memory.
+    OutOfRange { starting_address: usize, length: usize },
+    /// An Intel HEX record is malformed or its checksum doesn't match. Lines are numbered from 1.
+    InvalidHexRecord { line: usize },
+}
+
+impl Display for MemoryLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MemoryLoadError::Io(error) => write!(f, "{}", error),
+            MemoryLoadError::OutOfRange {
+                starting_address,
+                length,
+            } => write!(
+                f,
+                "{} bytes loaded at {:04X} would exceed the top of memory",
+                length, starting_address
+            ),
+            MemoryLoadError::InvalidHexRecord { line } => {
+                write!(f, "invalid Intel HEX record on line {}", line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MemoryLoadError {}
+
+impl From<std::io::Error> for MemoryLoadError {
+    fn from(error: std::io::Error) -> MemoryLoadError {
+        MemoryLoadError::Io(error)
+    }
+}
+
 pub struct RomRegion {
     pub start: usize,
     pub end: usize,
 }
 
+/// A closure that supplies the value read from a hooked address.
+pub struct ReadHook {
+    pub start: usize,
+    pub end: usize,
+    pub priority: i32,
+    pub hook: Box<dyn Fn(usize) -> u8>,
+}
+
+/// A closure that receives the value written to a hooked address.
+pub struct WriteHook {
+    pub start: usize,
+    pub end: usize,
+    pub priority: i32,
+    pub hook: Box<dyn FnMut(usize, u8)>,
+}
+
+/// A closure that receives the writes to a ROM region, such as a cartridge's mapper registers.
+pub struct Mapper {
+    pub start: usize,
+    pub end: usize,
+    pub callback: Box<dyn FnMut(usize, u8)>,
+}
+
 pub struct Memory {
     pub contents: [u8; SIXTY_FOUR_K_BYTES],
     pub rom_regions: Vec<RomRegion>,
+    read_hooks: Vec<ReadHook>,
+    write_hooks: Vec<WriteHook>,
+    mappers: Vec<Mapper>,
 }
 
 impl Memory {
@@ -18,9 +85,184 @@ impl Memory {
         Memory {
             contents: [0x00u8; SIXTY_FOUR_K_BYTES],
             rom_regions: Vec::new(),
+            read_hooks: Vec::new(),
+            write_hooks: Vec::new(),
+            mappers: Vec::new(),
         }
     }
 
+    /// Returns a `Memory` with `data` loaded at `load_addr` and zeros everywhere else.
+    ///
+    /// Returns `MemoryLoadError::OutOfRange` if `data` extends past the top of memory.
+    pub fn from_bytes(data: &[u8], load_addr: u16) -> Result<Memory, MemoryLoadError> {
+        let mut memory = Memory::new();
+
+        memory.apply_patch(load_addr, data)?;
+
+        Ok(memory)
+    }
+
+    /// Routes reads from `start..=end` to `hook` instead of `contents`.
+    /// This is how memory-mapped I/O registers are emulated.
+    /// The hook is added with a priority of 0.
+    pub fn add_read_hook(&mut self, start: usize, end: usize, hook: impl Fn(usize) -> u8 + 'static) {
+        self.add_read_hook_with_priority(start, end, 0, hook);
+    }
+
+    /// Routes reads from `start..=end` to `hook` instead of `contents`.
+    ///
+    /// When hooks overlap, only the one with the highest `priority` is called.
+    /// If several share the highest priority, the most recently added one wins.
+    pub fn add_read_hook_with_priority(
+        &mut self,
+        start: usize,
+        end: usize,
+        priority: i32,
+        hook: impl Fn(usize) -> u8 + 'static,
+    ) {
+        let index = self
+            .read_hooks
+            .iter()
+            .position(|read_hook| read_hook.priority <= priority)
+            .unwrap_or(self.read_hooks.len());
+
+        self.read_hooks.insert(
+            index,
+            ReadHook {
+                start,
+                end,
+                priority,
+                hook: Box::new(hook),
+            },
+        );
+    }
+
+    /// Routes writes to `start..=end` to `hook` instead of `contents`.
+    /// This is how memory-mapped I/O registers are emulated.
+    /// The hook is added with a priority of 0.
+    pub fn add_write_hook(&mut self, start: usize, end: usize, hook: impl FnMut(usize, u8) + 'static) {
+        self.add_write_hook_with_priority(start, end, 0, hook);
+    }
+
+    /// Routes writes to `start..=end` to `hook` instead of `contents`.
+    ///
+    /// When hooks overlap, only the one with the highest `priority` is called.
+    /// If several share the highest priority, the most recently added one wins.
+    pub fn add_write_hook_with_priority(
+        &mut self,
+        start: usize,
+        end: usize,
+        priority: i32,
+        hook: impl FnMut(usize, u8) + 'static,
+    ) {
+        let index = self
+            .write_hooks
+            .iter()
+            .position(|write_hook| write_hook.priority <= priority)
+            .unwrap_or(self.write_hooks.len());
+
+        self.write_hooks.insert(
+            index,
+            WriteHook {
+                start,
+                end,
+                priority,
+                hook: Box::new(hook),
+            },
+        );
+    }
+
+    /// Maps `start..=end` onto a buffer that can be shared with the memory of other cpus,
+    /// such as the RAM of a dual-cpu system. `start` maps to the first byte of the buffer.
+    ///
+    /// The region is implemented with a read and a write hook of priority 0, so `contents`
+    /// doesn't reflect it.
+    ///
+    /// Each `Cpu` owns its `Memory` and reads `contents` directly on its hot paths, so
+    /// rather than sharing a whole `Memory` through an `Rc<RefCell<Memory>>` or
+    /// `Arc<Mutex<Memory>>`, which would need a generic `Cpu` and a borrow or lock on every
+    /// access, only the shared RAM is put behind the `Rc<RefCell<>>`. The concurrency
+    /// model follows from the `Rc`: the cpus must run on the same thread and take turns,
+    /// for example by calling `Cpu::step_until_cycles()` on each in a loop. Each one sees the
+    /// other's writes as soon as they're made and no locking is needed. Cpus on different
+    /// threads aren't supported.
+    ///
+    /// # Arguments
+    /// * `start` - The first address of the region.
+    /// * `end` - The last address of the region.
+    /// * `shared` - The shared buffer, which must hold at least `end - start + 1` bytes.
+    ///
+    /// # Panics
+    /// Panics if `end` is before `start` or the shared buffer is too small for the region.
+    ///
+    /// # Examples
+    /// ```
+    /// use rust_6502::cpu::memory::Memory;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let shared = Rc::new(RefCell::new(vec![0u8; 0x1000]));
+    ///
+    /// let mut first = Memory::new();
+    /// let mut second = Memory::new();
+    /// first.add_shared_region(0x2000, 0x2FFF, shared.clone());
+    /// second.add_shared_region(0x6000, 0x6FFF, shared.clone());
+    ///
+    /// first.set_8_bit_value(0x2010, 0x42);
+    ///
+    /// assert_eq!(second.get_8_bit_value(0x6010), 0x42);
+    /// ```
+    pub fn add_shared_region(&mut self, start: usize, end: usize, shared: Rc<RefCell<Vec<u8>>>) {
+        assert!(
+            start <= end,
+            "The shared region ends at {:04X}, before it starts at {:04X}",
+            end,
+            start
+        );
+        assert!(
+            shared.borrow().len() > end - start,
+            "The shared buffer is smaller than {:04X}-{:04X}",
+            start,
+            end
+        );
+
+        let reads = shared.clone();
+
+        self.add_read_hook(start, end, move |address| reads.borrow()[address - start]);
+        self.add_write_hook(start, end, move |address, value| {
+            shared.borrow_mut()[address - start] = value;
+        });
+    }
+
+    /// Makes `start..=end` a ROM region whose writes are passed to `callback` instead of
+    /// being ignored, which is how cartridge mappers select banks. Unlike a write hook,
+    /// reads still return the ROM in `contents`, and write hooks still take precedence.
+    ///
+    /// # Arguments
+    /// * `start` - The first address of the region.
+    /// * `end` - The last address of the region.
+    /// * `callback` - Called with the address and value of every write to the region.
+    pub fn add_mapper(&mut self, start: usize, end: usize, callback: impl FnMut(usize, u8) + 'static) {
+        self.rom_regions.push(RomRegion { start, end });
+
+        self.mappers.push(Mapper {
+            start,
+            end,
+            callback: Box::new(callback),
+        });
+    }
+
+    /// Returns `true` if a read or write hook covers `address`.
+    pub fn is_hooked(&self, address: usize) -> bool {
+        self.read_hooks
+            .iter()
+            .any(|hook| address >= hook.start && address <= hook.end)
+            || self
+                .write_hooks
+                .iter()
+                .any(|hook| address >= hook.start && address <= hook.end)
+    }
+
     pub fn create_page_hexdump(&self, page: u8) -> String {
         let mut result = String::new();
         let mut address: usize = (page as usize) << 8;
@@ -62,28 +304,173 @@ impl Memory {
     }
 
     pub fn get_8_bit_value(&self, address: usize) -> u8 {
+        if let Some(read_hook) = self
+            .read_hooks
+            .iter()
+            .find(|hook| address >= hook.start && address <= hook.end)
+        {
+            return (read_hook.hook)(address);
+        }
+
         self.contents[address]
     }
 
     pub fn get_16_bit_value(&self, address: usize) -> u16 {
-        let lsb = self.contents[address];
-        let msb = self.contents[address + 1];
+        let lsb = self.get_8_bit_value(address);
+        let msb = self.get_8_bit_value((address + 1) & 0xFFFF);
 
         (msb as u16) << 8 | lsb as u16
     }
 
     pub fn read_raw_file_into_memory(&mut self, file_path: &str, starting_address: usize) -> usize {
-        if let Ok(mut file) = File::open(file_path) {
-            let mut buffer = Vec::new();
+        self.load_raw_file(file_path, starting_address).unwrap_or(0)
+    }
 
-            if let Ok(length) = file.read_to_end(&mut buffer) {
-                self.save_u8_vector_into_memory(starting_address, buffer);
+    /// Loads a flat binary file into memory.
+    ///
+    /// Returns the number of bytes loaded or a `MemoryLoadError` if the file
+    /// can't be read or doesn't fit in memory.
+    pub fn load_raw_file(&mut self, file_path: &str, starting_address: usize) -> Result<usize, MemoryLoadError> {
+        let mut buffer = Vec::new();
+
+        let length = File::open(file_path)?.read_to_end(&mut buffer)?;
+
+        if starting_address + length > SIXTY_FOUR_K_BYTES {
+            return Err(MemoryLoadError::OutOfRange {
+                starting_address,
+                length,
+            });
+        }
 
-                return length;
+        self.save_u8_vector_into_memory(starting_address, buffer);
+
+        Ok(length)
+    }
+
+    /// Loads an Intel HEX file into memory.
+    ///
+    /// Returns the number of data bytes loaded or a `MemoryLoadError` if the file
+    /// can't be read, has an invalid record or doesn't fit in memory.
+    pub fn load_intel_hex(&mut self, file_path: &str) -> Result<usize, MemoryLoadError> {
+        let mut hex = String::new();
+
+        File::open(file_path)?.read_to_string(&mut hex)?;
+
+        self.load_intel_hex_str(&hex)
+    }
+
+    /// Loads Intel HEX records into memory. Extended segment address (02) and extended
+    /// linear address (04) records set the base added to the addresses of the data records
+    /// that follow, which must still land within the 64K address space.
+    ///
+    /// Returns the number of data bytes loaded.
+    pub fn load_intel_hex_str(&mut self, hex: &str) -> Result<usize, MemoryLoadError> {
+        let mut base_address = 0usize;
+        let mut total_length = 0;
+
+        for (index, line) in hex.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let line_number = index + 1;
+
+            let bytes = line
+                .strip_prefix(':')
+                .filter(|record| record.len() % 2 == 0 && record.len() >= 10)
+                .and_then(|record| {
+                    (0..record.len())
+                        .step_by(2)
+                        .map(|i| u8::from_str_radix(record.get(i..i + 2)?, 16).ok())
+                        .collect::<Option<Vec<u8>>>()
+                })
+                .ok_or(MemoryLoadError::InvalidHexRecord { line: line_number })?;
+
+            let length = bytes[0] as usize;
+
+            if bytes.len() != length + 5
+                || bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0
+            {
+                return Err(MemoryLoadError::InvalidHexRecord { line: line_number });
             }
+
+            let address = (bytes[1] as usize) << 8 | bytes[2] as usize;
+            let data = &bytes[4..4 + length];
+
+            match bytes[3] {
+                0x00 => {
+                    let starting_address = base_address + address;
+
+                    if starting_address + length > SIXTY_FOUR_K_BYTES {
+                        return Err(MemoryLoadError::OutOfRange {
+                            starting_address,
+                            length,
+                        });
+                    }
+
+                    self.save_u8_vector_into_memory(starting_address, data.to_vec());
+
+                    total_length += length;
+                }
+                0x01 => break,
+                0x02 if length == 2 => {
+                    base_address = ((data[0] as usize) << 8 | data[1] as usize) << 4;
+                }
+                0x04 if length == 2 => {
+                    base_address = ((data[0] as usize) << 8 | data[1] as usize) << 16;
+                }
+                // Start segment (03) and start linear (05) addresses don't affect memory.
+                0x03 | 0x05 => {}
+                _ => return Err(MemoryLoadError::InvalidHexRecord { line: line_number }),
+            }
+        }
+
+        Ok(total_length)
+    }
+
+    /// Writes a patch, such as a cheat, into memory and returns the bytes it replaced so
+    /// it can be undone with `revert_patch()`. Patches write `contents` directly, so they
+    /// also apply to ROM regions and bypass write hooks.
+    ///
+    /// Returns a `MemoryLoadError::OutOfRange` without changing memory if the patch
+    /// would extend past $FFFF.
+    ///
+    /// # Arguments
+    /// * `address` - The address of the first patched byte.
+    /// * `bytes` - The bytes to write.
+    pub fn apply_patch(&mut self, address: u16, bytes: &[u8]) -> Result<Vec<u8>, MemoryLoadError> {
+        let starting_address = address as usize;
+        let end = starting_address + bytes.len();
+
+        if end > SIXTY_FOUR_K_BYTES {
+            return Err(MemoryLoadError::OutOfRange {
+                starting_address,
+                length: bytes.len(),
+            });
         }
 
-        0
+        let previous = self.contents[starting_address..end].to_vec();
+
+        self.contents[starting_address..end].copy_from_slice(bytes);
+
+        Ok(previous)
+    }
+
+    /// Restores the bytes returned by `apply_patch()`.
+    ///
+    /// # Arguments
+    /// * `address` - The address the patch was applied at.
+    /// * `previous` - The bytes `apply_patch()` returned.
+    pub fn revert_patch(&mut self, address: u16, previous: &[u8]) -> Result<(), MemoryLoadError> {
+        self.apply_patch(address, previous).map(|_| ())
+    }
+
+    /// Zeroes every byte of memory, including the vectors, so a `Memory` can be reused
+    /// without allocating a new one. Hooks, mappers and ROM regions are kept.
+    pub fn clear(&mut self) {
+        self.contents.fill(0x00);
     }
 
     pub fn save_u8_vector_into_memory(&mut self, starting_address: usize, vector: Vec<u8>) {
@@ -92,20 +479,69 @@ impl Memory {
         }
     }
 
+    /// Returns a borrowed view of memory from `start` to `end` inclusive.
+    ///
+    /// The view reads `contents` directly and bypasses any read hooks.
+    pub fn slice(&self, start: u16, end: u16) -> &[u8] {
+        &self.contents[start as usize..=end as usize]
+    }
+
     pub fn set_8_bit_value(&mut self, address: usize, value: u8) {
+        if let Some(write_hook) = self
+            .write_hooks
+            .iter_mut()
+            .find(|hook| address >= hook.start && address <= hook.end)
+        {
+            (write_hook.hook)(address, value);
+            return;
+        }
+
         if !self.is_in_rom_region(address) {
             self.contents[address] = value;
+        } else if let Some(mapper) = self
+            .mappers
+            .iter_mut()
+            .find(|mapper| address >= mapper.start && address <= mapper.end)
+        {
+            (mapper.callback)(address, value);
         }
     }
 
     pub fn set_16_bit_value(&mut self, address: usize, value: u16) {
-        if !self.is_in_rom_region(address) {
-            let lsb = (value as u16) & 0x00ff;
-            let msb = (value as u16) >> 8;
+        let lsb = value & 0x00ff;
+        let msb = value >> 8;
 
-            self.contents[address] = lsb as u8;
-            self.contents[address + 1] = msb as u8;
-        }
+        self.set_8_bit_value(address, lsb as u8);
+        self.set_8_bit_value((address + 1) & 0xFFFF, msb as u8);
+    }
+
+    /// Reads a byte from the zero page.
+    pub fn zp_read(&self, address: u8) -> u8 {
+        self.get_8_bit_value(address as usize)
+    }
+
+    /// Writes a byte to the zero page.
+    pub fn zp_write(&mut self, address: u8, value: u8) {
+        self.set_8_bit_value(address as usize, value);
+    }
+
+    /// Reads a 16-bit pointer from the zero page, least significant byte first.
+    /// A pointer read at `$FF` takes its most significant byte from `$00`, as the
+    /// 6502's indirect addressing modes do.
+    pub fn zp_read_u16(&self, address: u8) -> u16 {
+        let lsb = self.zp_read(address);
+        let msb = self.zp_read(address.wrapping_add(1));
+
+        (msb as u16) << 8 | lsb as u16
+    }
+
+    /// Writes a 16-bit pointer to the zero page, least significant byte first.
+    /// A pointer written at `$FF` has its most significant byte wrap to `$00`.
+    /// No instruction writes a 16-bit value to the zero page, so this is for setting up
+    /// pointers before running a program.
+    pub fn zp_write_u16(&mut self, address: u8, value: u16) {
+        self.zp_write(address, value as u8);
+        self.zp_write(address.wrapping_add(1), (value >> 8) as u8);
     }
 
     fn is_in_rom_region(&mut self, address: usize) -> bool {
@@ -134,6 +570,22 @@ mod tests {
         assert_eq!(value, 0xff);
     }
 
+    #[test]
+    fn test_from_bytes() {
+        let memory = Memory::from_bytes(&[0xA9, 0x01, 0x60], 0x0400).unwrap();
+
+        assert_eq!(memory.slice(0x03FF, 0x0403), &[0x00, 0xA9, 0x01, 0x60, 0x00]);
+        assert_eq!(memory.contents.iter().filter(|byte| **byte != 0x00).count(), 3);
+
+        assert!(matches!(
+            Memory::from_bytes(&[0xEA, 0xEA], 0xFFFF),
+            Err(MemoryLoadError::OutOfRange {
+                starting_address: 0xFFFF,
+                length: 2
+            })
+        ));
+    }
+
     #[test]
     fn test_set_8_bit_value() {
         let mut memory = Memory::new();
@@ -170,6 +622,74 @@ mod tests {
         assert_eq!(memory.contents[1], 0x2c);
     }
 
+    #[test]
+    fn test_zp_write_u16() {
+        let mut memory = Memory::new();
+
+        memory.zp_write_u16(0xff, 0x2cfd);
+
+        assert_eq!(memory.contents[0xff], 0xfd);
+        assert_eq!(memory.contents[0x00], 0x2c);
+        assert_eq!(memory.contents[0x100], 0x00);
+    }
+
+    #[test]
+    fn test_read_hook() {
+        let mut memory = Memory::new();
+
+        memory.add_read_hook(0xD000, 0xD0FF, |address| (address & 0xFF) as u8);
+
+        assert_eq!(memory.get_8_bit_value(0xD012), 0x12);
+        assert_eq!(memory.contents[0xD012], 0x00);
+        assert!(memory.is_hooked(0xD0FF));
+        assert!(!memory.is_hooked(0xD100));
+    }
+
+    #[test]
+    fn test_write_hook() {
+        let mut memory = Memory::new();
+
+        let written = std::rc::Rc::new(std::cell::Cell::new((0usize, 0u8)));
+        let hook_written = written.clone();
+
+        memory.add_write_hook(0xD020, 0xD020, move |address, value| {
+            hook_written.set((address, value))
+        });
+
+        memory.set_8_bit_value(0xD020, 0x06);
+        memory.set_8_bit_value(0xD021, 0x0E);
+
+        assert_eq!(written.get(), (0xD020, 0x06));
+        assert_eq!(memory.contents[0xD020], 0x00);
+        assert_eq!(memory.contents[0xD021], 0x0E);
+    }
+
+    #[test]
+    fn test_overlapping_write_hooks_use_priority() {
+        let mut memory = Memory::new();
+
+        let receiver = std::rc::Rc::new(std::cell::Cell::new(""));
+        let (low, high, later) = (receiver.clone(), receiver.clone(), receiver.clone());
+
+        memory.add_write_hook_with_priority(0xD000, 0xD3FF, 0, move |_, _| low.set("low"));
+        memory.add_write_hook_with_priority(0xD020, 0xD02F, 10, move |_, _| high.set("high"));
+
+        memory.set_8_bit_value(0xD020, 0x01);
+        assert_eq!(receiver.get(), "high");
+
+        memory.set_8_bit_value(0xD000, 0x01);
+        assert_eq!(receiver.get(), "low");
+
+        // Equal priorities resolve to the most recently added hook.
+        memory.add_write_hook_with_priority(0xD000, 0xD3FF, 0, move |_, _| later.set("later"));
+
+        memory.set_8_bit_value(0xD000, 0x01);
+        assert_eq!(receiver.get(), "later");
+
+        memory.set_8_bit_value(0xD020, 0x01);
+        assert_eq!(receiver.get(), "high");
+    }
+
     #[test]
     fn test_is_in_rom_region() {
         let mut memory = Memory::new();
@@ -187,6 +707,179 @@ mod tests {
         assert!(!memory.is_in_rom_region(0x3002));
     }
 
+    #[test]
+    #[should_panic(expected = "The shared region ends at 1000, before it starts at 2000")]
+    fn test_add_shared_region_ending_before_it_starts() {
+        let mut memory = Memory::new();
+
+        memory.add_shared_region(0x2000, 0x1000, Rc::new(RefCell::new(vec![0u8; 0x1000])));
+    }
+
+    #[test]
+    fn test_add_mapper() {
+        let writes = Rc::new(RefCell::new(Vec::new()));
+        let mapper_writes = writes.clone();
+
+        let mut memory = Memory::new();
+        memory.contents[0x8000] = 0x4C;
+
+        memory.add_mapper(0x8000, 0xFFFF, move |address, value| {
+            mapper_writes.borrow_mut().push((address, value))
+        });
+
+        memory.set_8_bit_value(0x8000, 0x03);
+        memory.set_8_bit_value(0x7FFF, 0x05);
+
+        assert_eq!(*writes.borrow(), vec![(0x8000, 0x03)]);
+        assert_eq!(memory.get_8_bit_value(0x8000), 0x4C);
+        assert_eq!(memory.get_8_bit_value(0x7FFF), 0x05);
+    }
+
+    #[test]
+    fn test_load_raw_file_out_of_range() {
+        let mut memory = Memory::new();
+
+        let file_path = std::env::temp_dir().join("rust_6502_test_load_raw_file_out_of_range.bin");
+        std::fs::write(&file_path, [0xEA, 0xEA, 0xEA]).unwrap();
+
+        let result = memory.load_raw_file(file_path.to_str().unwrap(), 0xFFFE);
+
+        std::fs::remove_file(&file_path).unwrap();
+
+        assert!(matches!(
+            result,
+            Err(MemoryLoadError::OutOfRange {
+                starting_address: 0xFFFE,
+                length: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn test_load_raw_file_missing_file() {
+        let mut memory = Memory::new();
+
+        let result = memory.load_raw_file("this_file_does_not_exist.bin", 0x0000);
+
+        assert!(matches!(result, Err(MemoryLoadError::Io(_))));
+    }
+
+    #[test]
+    fn test_load_intel_hex_str() {
+        let mut memory = Memory::new();
+
+        let hex = ":020000040000FA\n\
+                   :03100000010203E7\n\
+                   :00000001FF\n";
+
+        assert_eq!(memory.load_intel_hex_str(hex).unwrap(), 3);
+        assert_eq!(memory.contents[0x1000..0x1003], [0x01, 0x02, 0x03]);
+    }
+
+    #[test]
+    fn test_load_intel_hex_str_extended_segment_address() {
+        let mut memory = Memory::new();
+
+        // A segment of $0100 puts the data at $1000 + $0010.
+        let hex = ":020000020100FB\n\
+                   :01001000AA45\n\
+                   :00000001FF\n";
+
+        assert_eq!(memory.load_intel_hex_str(hex).unwrap(), 1);
+        assert_eq!(memory.contents[0x1010], 0xAA);
+    }
+
+    #[test]
+    fn test_load_intel_hex_str_errors() {
+        let mut memory = Memory::new();
+
+        // A linear base of $10000 is past the top of memory.
+        let hex = ":020000040001F9\n\
+                   :01000000AA55\n";
+
+        assert!(matches!(
+            memory.load_intel_hex_str(hex),
+            Err(MemoryLoadError::OutOfRange {
+                starting_address: 0x10000,
+                length: 1
+            })
+        ));
+
+        // The checksum should be $55.
+        assert!(matches!(
+            memory.load_intel_hex_str(":01000000AA56\n"),
+            Err(MemoryLoadError::InvalidHexRecord { line: 1 })
+        ));
+
+        assert!(matches!(
+            memory.load_intel_hex_str(":00000001FF\n01000000AA55\n"),
+            Ok(0)
+        ));
+
+        assert!(matches!(
+            memory.load_intel_hex_str("\n01000000AA55\n"),
+            Err(MemoryLoadError::InvalidHexRecord { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_apply_and_revert_patch() {
+        let mut memory = Memory::new();
+        memory.save_u8_vector_into_memory(0x8000, vec![0xA9, 0x03, 0x85, 0x10]);
+        memory.rom_regions.push(RomRegion {
+            start: 0x8000,
+            end: 0xFFFF,
+        });
+
+        let previous = memory.apply_patch(0x8001, &[0x09, 0xEA]).unwrap();
+
+        assert_eq!(previous, vec![0x03, 0x85]);
+        assert_eq!(memory.slice(0x8000, 0x8003), &[0xA9, 0x09, 0xEA, 0x10]);
+
+        memory.revert_patch(0x8001, &previous).unwrap();
+
+        assert_eq!(memory.slice(0x8000, 0x8003), &[0xA9, 0x03, 0x85, 0x10]);
+
+        assert!(matches!(
+            memory.apply_patch(0xFFFF, &[0x01, 0x02]),
+            Err(MemoryLoadError::OutOfRange {
+                starting_address: 0xFFFF,
+                length: 2
+            })
+        ));
+        assert_eq!(memory.contents[0xFFFF], 0x00);
+    }
+
+    #[test]
+    fn test_zero_page_accessors() {
+        let mut memory = Memory::new();
+
+        memory.zp_write(0x10, 0x34);
+        memory.zp_write(0x11, 0x12);
+
+        assert_eq!(memory.zp_read(0x10), 0x34);
+        assert_eq!(memory.zp_read_u16(0x10), 0x1234);
+
+        memory.zp_write(0xFF, 0x78);
+        memory.zp_write(0x00, 0x56);
+        memory.contents[0x0100] = 0x99;
+
+        assert_eq!(memory.zp_read_u16(0xFF), 0x5678);
+    }
+
+    #[test]
+    fn test_slice() {
+        let mut memory = Memory::new();
+        memory.contents[0x0400] = 0x01;
+        memory.contents[0x07ff] = 0x02;
+
+        let screen = memory.slice(0x0400, 0x07ff);
+
+        assert_eq!(screen.len(), 0x0400);
+        assert_eq!(screen[0], 0x01);
+        assert_eq!(screen[0x03ff], 0x02);
+    }
+
     #[test]
     fn test_save_u8_vector_into_memory() {
         let mut memory = Memory::new();
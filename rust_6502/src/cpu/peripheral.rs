@@ -0,0 +1,10 @@
+use super::interrupt::InterruptKind;
+use super::memory::Memory;
+
+/// A device, such as a CIA, VIA or PIA timer, that runs alongside the cpu.
+pub trait Peripheral {
+    /// Called after every instruction and interrupt with the number of cycles it took.
+    ///
+    /// Returning an `InterruptKind` asserts that interrupt line on the cpu.
+    fn tick(&mut self, cycles: u8, memory: &mut Memory) -> Option<InterruptKind>;
+}
@@ -2,6 +2,16 @@ use std::fmt::Display;
 
 use super::status_flags::StatusFlags;
 
+/// An 8-bit register, used by `Cpu::break_when_register()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Register {
+    A,
+    X,
+    Y,
+    Sp,
+    P,
+}
+
 pub struct Registers {
     pub a: u8,
     pub x: u8,
@@ -22,6 +32,17 @@ impl Registers {
             pc: 0,
         }
     }
+
+    /// Returns the value of an 8-bit register.
+    pub fn value(&self, register: Register) -> u8 {
+        match register {
+            Register::A => self.a,
+            Register::X => self.x,
+            Register::Y => self.y,
+            Register::Sp => self.sp,
+            Register::P => self.p.to_byte(),
+        }
+    }
 }
 
 impl Display for Registers {
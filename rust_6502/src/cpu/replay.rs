@@ -0,0 +1,37 @@
+/// An external input to the cpu captured by `Cpu::record()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    /// The NMI line was set or cleared.
+    Nmi(bool),
+    /// The IRQ line was set or cleared.
+    Irq(bool),
+    /// `Cpu::set_rdy()` was called.
+    Rdy(bool),
+}
+
+/// An input and the cycle count at which the cpu observed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputEvent {
+    pub cycles: u64,
+    pub input: Input,
+}
+
+/// The inputs recorded between `Cpu::record()` and `Cpu::stop_recording()`, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputLog {
+    pub events: Vec<InputEvent>,
+}
+
+/// Tracks a recording in progress.
+pub(crate) struct Recording {
+    pub log: InputLog,
+    /// The NMI and IRQ lines the last time the cpu polled them.
+    pub lines: (bool, bool),
+}
+
+/// Tracks a replay in progress.
+pub(crate) struct Replaying {
+    pub log: InputLog,
+    /// The index of the next event to apply.
+    pub next: usize,
+}
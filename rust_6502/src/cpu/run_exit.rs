@@ -0,0 +1,27 @@
+use super::error::CpuError;
+
+/// The reason `Cpu::run()` or `Cpu::run_headless()` stopped executing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunExit {
+    /// The debugger issued the `Q` (quit) command.
+    Quit,
+    /// The debugger issued the `P` (pause) command before executing the instruction at the
+    /// contained address. Calling `Cpu::run()` again resumes from there.
+    Paused(u16),
+    /// A `BRK` was executed at the contained address while the IRQ/BRK vector was $0000.
+    /// Only returned when `Cpu::halt_on_unhandled_brk` is `true`.
+    BreakWithoutHandler(u16),
+    /// The instruction at the contained address jumped or branched to itself. Only returned
+    /// by `Cpu::run_headless()`.
+    Trapped(u16),
+    /// An instruction trapped at `Cpu::success_address`. Only returned by `Cpu::run_headless()`.
+    Success,
+    /// The call depth went over `Cpu::max_call_depth`, leaving the program counter at the
+    /// contained address.
+    RecursionLimit(u16),
+    /// The JAM opcode at the contained address locked up the cpu. `Cpu::halted` stays set
+    /// until `Cpu::power_up()` or `Cpu::reset()` is called.
+    Jammed(u16),
+    /// Execution couldn't continue, for example because of an unrecognized opcode.
+    Error(CpuError),
+}
@@ -0,0 +1,37 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A copy of the cpu's registers, pending interrupts, cycle counter and memory
+/// that can be restored later with `Cpu::restore()`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CpuSnapshot {
+    pub a: u8,
+    pub x: u8,
+    pub y: u8,
+    /// The status register as a byte.
+    pub p: u8,
+    pub sp: u8,
+    pub pc: u16,
+    pub nmi_triggered: bool,
+    pub irq_triggered: bool,
+    pub cycles: u64,
+    /// The 64K of memory contents.
+    pub memory: Vec<u8>,
+}
+
+/// A copy of just the cpu's registers and cycle counter that can be restored later with
+/// `Cpu::restore_registers()`. It's much cheaper than a `CpuSnapshot` because it leaves
+/// out memory.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegistersSnapshot {
+    pub a: u8,
+    pub x: u8,
+    pub y: u8,
+    /// The status register as a byte.
+    pub p: u8,
+    pub sp: u8,
+    pub pc: u16,
+    pub cycles: u64,
+}
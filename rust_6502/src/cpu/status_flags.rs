@@ -1,5 +1,17 @@
 use std::fmt::Display;
 
+/// A single flag in the status register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    Carry,
+    Zero,
+    InterruptDisable,
+    Decimal,
+    Break,
+    Overflow,
+    Negative,
+}
+
 #[derive(Copy, Clone)]
 pub struct StatusFlags {
     pub carry_flag: bool,
@@ -33,6 +45,19 @@ impl StatusFlags {
         }
     }
 
+    /// Returns `true` if the flag is set.
+    pub fn is_set(&self, flag: Flag) -> bool {
+        match flag {
+            Flag::Carry => self.carry_flag,
+            Flag::Zero => self.zero_flag,
+            Flag::InterruptDisable => self.interrupt_disable_flag,
+            Flag::Decimal => self.decimal_flag,
+            Flag::Break => self.break_flag,
+            Flag::Overflow => self.overflow_flag,
+            Flag::Negative => self.negative_flag,
+        }
+    }
+
     pub fn from_byte(&mut self, byte: u8) {
         self.carry_flag = (byte & StatusFlags::CARRY_FLAG) != 0;
         self.zero_flag = (byte & StatusFlags::ZERO_FLAG) != 0;
@@ -78,6 +103,42 @@ impl StatusFlags {
 
         result
     }
+
+    /// Returns the status byte pushed onto the stack by `PHP`, `BRK` or an interrupt.
+    /// The unused bit is always set and the break bit is set only by `PHP` and `BRK`,
+    /// whatever the state of `break_flag`.
+    ///
+    /// # Arguments
+    /// * `break_set` - `true` for `PHP` and `BRK`, `false` for IRQ and NMI.
+    pub fn to_pushed_byte(&self, break_set: bool) -> u8 {
+        let result = (self.to_byte() & !StatusFlags::BREAK_FLAG) | StatusFlags::UNUSED_FLAG;
+
+        match break_set {
+            true => result | StatusFlags::BREAK_FLAG,
+            false => result,
+        }
+    }
+}
+
+impl From<u8> for StatusFlags {
+    fn from(byte: u8) -> Self {
+        let mut status_flags = StatusFlags::new();
+        status_flags.from_byte(byte);
+
+        status_flags
+    }
+}
+
+impl From<StatusFlags> for u8 {
+    fn from(status_flags: StatusFlags) -> Self {
+        status_flags.to_byte()
+    }
+}
+
+impl From<&StatusFlags> for u8 {
+    fn from(status_flags: &StatusFlags) -> Self {
+        status_flags.to_byte()
+    }
 }
 
 impl Display for StatusFlags {
@@ -119,6 +180,64 @@ impl Display for StatusFlags {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_set() {
+        let mut status_flags = StatusFlags::new();
+        status_flags.from_byte(StatusFlags::OVERFLOW_FLAG | StatusFlags::CARRY_FLAG);
+
+        assert!(status_flags.is_set(Flag::Carry));
+        assert!(status_flags.is_set(Flag::Overflow));
+        assert!(!status_flags.is_set(Flag::Zero));
+        assert!(!status_flags.is_set(Flag::InterruptDisable));
+        assert!(!status_flags.is_set(Flag::Decimal));
+        assert!(!status_flags.is_set(Flag::Break));
+        assert!(!status_flags.is_set(Flag::Negative));
+    }
+
+    #[test]
+    fn test_to_pushed_byte() {
+        let mut status_flags = StatusFlags::new();
+        status_flags.carry_flag = true;
+
+        assert_eq!(
+            status_flags.to_pushed_byte(false),
+            StatusFlags::UNUSED_FLAG | StatusFlags::CARRY_FLAG
+        );
+        assert_eq!(
+            status_flags.to_pushed_byte(true),
+            StatusFlags::UNUSED_FLAG | StatusFlags::BREAK_FLAG | StatusFlags::CARRY_FLAG
+        );
+
+        status_flags.break_flag = true;
+
+        assert_eq!(
+            status_flags.to_pushed_byte(false),
+            StatusFlags::UNUSED_FLAG | StatusFlags::CARRY_FLAG
+        );
+    }
+
+    #[test]
+    fn test_from_conversions() {
+        let status_flags = StatusFlags::from(StatusFlags::NEGATIVE_FLAG | StatusFlags::ZERO_FLAG);
+
+        assert!(status_flags.negative_flag);
+        assert!(status_flags.zero_flag);
+        assert!(!status_flags.carry_flag);
+        assert!(!status_flags.overflow_flag);
+
+        let byte: u8 = status_flags.into();
+
+        assert_eq!(
+            byte,
+            StatusFlags::NEGATIVE_FLAG | StatusFlags::UNUSED_FLAG | StatusFlags::ZERO_FLAG
+        );
+        assert_eq!(u8::from(&status_flags), byte);
+
+        // The unused bit is ignored when converting from a byte and always set in the result.
+        assert_eq!(u8::from(StatusFlags::from(0x00)), StatusFlags::UNUSED_FLAG);
+        assert_eq!(u8::from(StatusFlags::from(0xFF)), 0xFF);
+    }
+
     #[test]
     fn test_to_byte_for_no_carry() {
         let status_flags = StatusFlags::new();
@@ -0,0 +1,37 @@
+use std::fmt::Display;
+
+use super::decoded_instruction::DecodedInstruction;
+
+/// The state of the cpu just before an instruction executes, passed to the callback
+/// set with `Cpu::set_trace_callback()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceRecord {
+    pub pc: u16,
+    pub a: u8,
+    pub x: u8,
+    pub y: u8,
+    /// The status register as a byte.
+    pub p: u8,
+    pub sp: u8,
+    /// The cycle counter before the instruction executes.
+    pub cycles: u64,
+    /// The instruction about to execute. `None` if the opcode isn't recognized.
+    pub instruction: Option<DecodedInstruction>,
+}
+
+/// Shows the disassembled instruction followed by the registers and cycle counter, e.g.
+/// `8000  A9 05    LDA  #$05     A:00 X:00 Y:00 P:24 SP:FD CYC:7`.
+impl Display for TraceRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let instruction = match &self.instruction {
+            Some(instruction) => instruction.to_string(),
+            None => format!("{:04X} {:<9} ???", self.pc, ""),
+        };
+
+        write!(
+            f,
+            "{:<28} A:{:02X} X:{:02X} Y:{:02X} P:{:02X} SP:{:02X} CYC:{}",
+            instruction, self.a, self.x, self.y, self.p, self.sp, self.cycles
+        )
+    }
+}
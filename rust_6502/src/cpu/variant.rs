@@ -0,0 +1,18 @@
+/// The members of the 6502 family that can be emulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CpuVariant {
+    /// The original NMOS 6502.
+    Nmos6502,
+    /// The WDC 65C02, which adds instructions such as `BIT #imm`.
+    Wdc65C02,
+    /// The Ricoh 2A03 used by the NES, an NMOS 6502 without decimal mode. The decimal flag
+    /// can still be set and cleared but `ADC` and `SBC` always use binary arithmetic.
+    Ricoh2A03,
+}
+
+impl CpuVariant {
+    /// Returns `true` if `ADC` and `SBC` use decimal arithmetic while the decimal flag is set.
+    pub fn has_decimal_mode(&self) -> bool {
+        *self != CpuVariant::Ricoh2A03
+    }
+}